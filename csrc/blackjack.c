@@ -23,11 +23,13 @@ void printPlayerHand(Player* player) {
 	char* name = player_getName(player);
 	for (unsigned int ih = 0; ih < handCount; ih++) {
 		Hand* hand = player_getHandWithIndex(player, ih);
-		printf("%s's hand #%d (%s): %d points\n",
-			name,
-			ih + 1,
-			hand_isSet(hand) ? "set" : "playing",
-			hand_value(hand));
+		char* value = hand_valueToString(hand);
+		if (hand_isSet(hand)) {
+			printf("%s's hand #%d (set): %d points\n", name, ih + 1, hand_value(hand));
+		} else {
+			printf("%s's hand #%d (playing): %s points\n", name, ih + 1, value);
+		}
+		rust_freestr(value);
 		unsigned int cardCount = hand_cardCount(hand);
 		for (unsigned int ic = 0; ic < cardCount; ic++) {
 			Card* card = hand_getCardWithIndex(hand, ic);
@@ -42,6 +44,87 @@ void printPlayerHand(Player* player) {
 	rust_freestr(name);
 }
 
+void printDealerHand(Dealer* dealer) {
+	Hand* hand = dealer_getHand(dealer);
+	if (!hand) {
+		return;
+	}
+	printf("Dealer's hand: %d points\n", hand_value(hand));
+	unsigned int cardCount = hand_cardCount(hand);
+	for (unsigned int ic = 0; ic < cardCount; ic++) {
+		Card* card = hand_getCardWithIndex(hand, ic);
+		char* c = card_toString(card);
+		printf("%s%s", ic ? ", " : "", c);
+		rust_freestr(c);
+		rust_freecard(card);
+	}
+	rust_freehand(hand);
+	printf("\n");
+}
+
+Money readMoney() {
+	char input[500];
+	Money amount = 0;
+	fgets(input, sizeof(input), stdin);
+	input[strcspn(input, "\n")] = 0;
+	money_fromString(input, &amount);
+	return amount;
+}
+
+void printMoney(Money amount, bool showSign) {
+	char* str = money_toString(amount);
+	printf("%s%s", showSign && amount >= 0 ? "+" : "", str);
+	rust_freestr(str);
+}
+
+static const char* actionNames[] = {
+	"hit", "stand", "double", "split", "surrender", "insurance"
+};
+
+static const char* outcomeNames[] = {
+	"Win", "Lose", "Push", "Blackjack", "Surrender"
+};
+
+void offerInsurance(Table* table, unsigned int seat, unsigned int spot) {
+	char input[500];
+	Player* player = table_getSeat(table, seat);
+	if (spot >= player_handCount(player)) {
+		return;
+	}
+	char* name = player_getName(player);
+	Hand* hand = player_getHandWithIndex(player, spot);
+	if (hand_isBlackjack(hand)) {
+		printf("%s (spot %u): Take even money? [y/N]: ", name, spot + 1);
+		fgets(input, sizeof(input), stdin);
+		if (input[0] == 'y' || input[0] == 'Y') {
+			table_takeEvenMoneySpot(table, seat, spot);
+		}
+	} else {
+		while (1) {
+			printf("%s (spot %u): Insurance amount (0 to decline): ", name, spot + 1);
+			const Money amount = readMoney();
+			if (!amount || table_insureSpot(table, seat, spot, amount) == BJ_OK) {
+				break;
+			}
+			printf("Insurance can be at most half your wager.\n");
+		}
+	}
+	rust_freehand(hand);
+	rust_freestr(name);
+}
+
+void printBetError(int err) {
+	if (err == BJ_ERR_BELOW_MINIMUM) {
+		printf("Bet is below the table minimum.\n");
+	} else if (err == BJ_ERR_ABOVE_MAXIMUM) {
+		printf("Bet is above the table maximum.\n");
+	} else if (err == BJ_ERR_TOO_MANY_SPOTS) {
+		printf("No more spots are available.\n");
+	} else {
+		printf("Bet must be positive and within your balance.\n");
+	}
+}
+
 int main(int argc, char* argv[]) {
 	char input[500];
 	printf("Blackjack!\n");
@@ -49,97 +132,185 @@ int main(int argc, char* argv[]) {
 	printf("How many decks? ");
 	fgets(input, sizeof(input), stdin);
 	const int deckCount = (int)strtol(input, (char**)NULL, 0);
-	Deck* deck = deck_new(deckCount);
+	Rules* rules = rules_new();
+	Table* table = table_new(deckCount, rules);
+	rust_freerules(rules);
 
 	printf("How many players? ");
 	fgets(input, sizeof(input), stdin);
 	const int playerCount = (int)strtol(input, (char**)NULL, 0);
 
-	Player** players = malloc(playerCount * sizeof(Player*));
 	for (int i = 0; i < playerCount; i++) {
-		char* name = malloc(100);
+		char name[100];
 		printf("Enter name for player %d: ", i + 1);
-		fgets(name, 100, stdin);
+		fgets(name, sizeof(name), stdin);
 		name[strlen(name) - 1] = 0;
-		printf("Enter initial balance: ");
-		fgets(input, sizeof(input), stdin);
-		const int initialBal = (int)strtol(input, (char**)NULL, 0);
-		players[i] = player_new(name, 0, initialBal);
+		Player* player = NULL;
+		while (!player) {
+			printf("Enter initial balance: ");
+			const Money initialBal = readMoney();
+			// player_new takes ownership of the name, even if it fails
+			player = player_new(strdup(name), initialBal);
+			if (!player) {
+				printf("Not a valid balance.\n");
+			}
+		}
+		if (table_addPlayer(table, player) < 0) {
+			printf("Couldn't seat %s.\n", name);
+		}
 	}
 
-	char* dealerName = malloc(7);
-	sprintf(dealerName, "Dealer");
-	Player* dealer = player_new(dealerName, 1, -1);
-
 	while (1) {
-		deck_reset(deck);
-		for (Player** pplayer = players; pplayer < players + playerCount; pplayer++) {
-			Player* player = *pplayer;
-			char* name = player_getName(player);
-			printf("%s: Enter wager for this hand: ", name);
+		for (unsigned int seat = 0; seat < table_getSeatCount(table); seat++) {
+			char* name = player_getName(table_getSeat(table, seat));
+			while (1) {
+				printf("%s: Enter wager for this hand: ", name);
+				const Money bet = readMoney();
+				const int err = table_bet(table, seat, bet);
+				if (err == BJ_OK) {
+					break;
+				}
+				printBetError(err);
+			}
+			while (1) {
+				printf("%s: Bet on another spot? [y/N]: ", name);
+				fgets(input, sizeof(input), stdin);
+				if (input[0] != 'y' && input[0] != 'Y') {
+					break;
+				}
+				printf("Enter wager for this spot: ");
+				const int err = table_addSpot(table, seat, readMoney());
+				if (err != BJ_OK) {
+					printBetError(err);
+				}
+				if (err == BJ_ERR_TOO_MANY_SPOTS) {
+					break;
+				}
+			}
 			rust_freestr(name);
-			fgets(input, sizeof(input), stdin);
-			int bet = (int)strtol(input, (char**)NULL, 0);
-			player_bet(player, bet, deck);
 		}
-		player_bet(dealer, 0, deck);
+		table_closeBetting(table);
+		if (table_needsShuffle(table)) {
+			printf("Shuffling the shoe\n");
+		}
+		if (table_deal(table) != BJ_OK) {
+			printf("Failed to deal. Bets are off, please bet again.\n");
+			continue;
+		}
 
-		for (Player** pplayer = players; pplayer < players + playerCount; pplayer++) {
-			Player* player = *pplayer;
-			printPlayerHand(player);
-			while (player_isPlaying(player)) {
-				printf("> ");
-				fgets(input, sizeof(input), stdin);
-				input[strlen(input) - 1] = 0;
-				if (!strcmp(input, "hit")) {
-					player_hit(player, deck);
-					if (player_isPlaying(player)) {
-						printPlayerHand(player);
-					}
-				} else if (!strcmp(input, "stand")) {
-					player_stand(player);
-					if (player_isPlaying(player)) {
-						printPlayerHand(player);
+		Dealer* dealer = table_getDealer(table);
+		Card* upcard = dealer_getUpcard(dealer);
+		char* upcardStr = card_toString(upcard);
+		printf("Dealer shows %s\n", upcardStr);
+		rust_freestr(upcardStr);
+		rust_freecard(upcard);
+		if (table_getPhase(table) == PHASE_INSURANCE) {
+			for (unsigned int seat = 0; seat < table_getSeatCount(table); seat++) {
+				for (unsigned int spot = 0; spot < table_getSpotCount(table, seat); spot++) {
+					offerInsurance(table, seat, spot);
+				}
+			}
+			table_closeInsurance(table);
+		}
+		if (table_getPhase(table) == PHASE_EARLY_SURRENDER) {
+			for (unsigned int seat = 0; seat < table_getSeatCount(table); seat++) {
+				Player* player = table_getSeat(table, seat);
+				for (unsigned int spot = 0; spot < table_getSpotCount(table, seat); spot++) {
+					if (!player_canSurrenderSpot(player, spot)) {
+						continue;
 					}
-				} else if (!strcmp(input, "surrender")) {
-					player_surrender(player);
-				} else if (!strcmp(input, "split")) {
-					if (player_split(player, deck)) {
-						printPlayerHand(player);
-					} else {
-						printf("Can't split this hand.\n");
+					char* name = player_getName(player);
+					printf("%s (spot %u): Surrender? [y/N]: ", name, spot + 1);
+					fgets(input, sizeof(input), stdin);
+					if (input[0] == 'y' || input[0] == 'Y') {
+						table_surrenderSpot(table, seat, spot);
 					}
-				} else if (!strcmp(input, "double")) {
-					player_double(player, deck);
-				} else if (!strcmp(input, "help")) {
-					printf("Commands: hit, stand, surrender, split, double\n");
-				} else {
-					printf("Unknown command. Type 'help' for a list of available choices.\n");
+					rust_freestr(name);
 				}
 			}
-			printPlayerHand(player);
+			table_closeSurrender(table);
 		}
-		int dealerPlays = 0;
-		for (Player** pplayer = players; pplayer < players + playerCount; pplayer++) {
-			if (!player_hasLost(*pplayer)) {
-				dealerPlays = 1;
-				break;
+		if (table_getPhase(table) == PHASE_SETTLEMENT) {
+			printf("Dealer has blackjack\n");
+			printDealerHand(dealer);
+		}
+
+		int currentSeat = -1;
+		while (table_getPhase(table) == PHASE_PLAYER_TURN) {
+			const int seat = table_getActiveSeat(table);
+			Player* player = table_getSeat(table, seat);
+			if (seat != currentSeat) {
+				printPlayerHand(player);
+				currentSeat = seat;
+			}
+			printf("> ");
+			fgets(input, sizeof(input), stdin);
+			input[strlen(input) - 1] = 0;
+			bool accepted = false;
+			if (!strcmp(input, "help")) {
+				const unsigned int actions = table_legalActions(table, seat);
+				printf("Commands:");
+				for (unsigned int action = ACTION_HIT; action <= ACTION_INSURANCE; action++) {
+					if (actions & (1 << action)) {
+						printf(" %s", actionNames[action]);
+					}
+				}
+				printf("\n");
+				continue;
+			}
+			int action = -1;
+			for (unsigned int i = ACTION_HIT; i <= ACTION_INSURANCE; i++) {
+				if (!strcmp(input, actionNames[i])) {
+					action = i;
+				}
+			}
+			if (action < 0) {
+				printf("Unknown command. Type 'help' for a list of available choices.\n");
+			} else if (!(accepted = table_apply(table, seat, action) == BJ_OK)) {
+				printf("Can't %s this hand now.\n", actionNames[action]);
+			}
+			if (accepted) {
+				printPlayerHand(player);
 			}
 		}
-		unsigned int dealerValue = 0;
-		if (dealerPlays) {
+
+		if (table_getPhase(table) == PHASE_DEALER_TURN) {
 			printf("Dealer's turn\n");
-			dealerValue = player_playAsDealer(dealer, deck);
-			printPlayerHand(dealer);
+			if (table_playDealer(table) != BJ_OK) {
+				printf("The shoe ran out. The round is void, please bet again.\n");
+				table_voidRound(table);
+				continue;
+			}
+			printDealerHand(dealer);
 		}
-		for (Player** pplayer = players; pplayer < players + playerCount; pplayer++) {
-			Player* player = *pplayer;
-			player_gameOver(player, dealerValue);
+		table_settle(table);
+		for (unsigned int seat = 0; seat < table_getSeatCount(table); seat++) {
+			Player* player = table_getSeat(table, seat);
 			char* name = player_getName(player);
-			printf("%s's balance, standing: %d/%d\n", name,  player_getBalance(player), player_getStanding(player));
+			SettlementReport* report = table_getSettlement(table, seat);
+			for (unsigned int ih = 0; ih < settlement_handCount(report); ih++) {
+				HandReport* hand = settlement_getHand(report, ih);
+				printf("%s's hand #%d (%d points, wager ", name, ih + 1, handReport_value(hand));
+				printMoney(handReport_wager(hand), false);
+				printf("): %s ", outcomeNames[handReport_outcome(hand)]);
+				printMoney(handReport_payout(hand), true);
+				printf("\n");
+			}
+			if (settlement_insurancePayout(report)) {
+				printf("%s's insurance: ", name);
+				printMoney(settlement_insurancePayout(report), true);
+				printf("\n");
+			}
+			printf("%s's balance, standing: ", name);
+			printMoney(player_getBalance(player), false);
+			printf("/");
+			printMoney(player_getStanding(player), false);
+			printf("\n");
 			rust_freestr(name);
 		}
-		player_gameOver(dealer, 0);
+		printf("House standing: ");
+		printMoney(dealer_getStanding(dealer), false);
+		printf("\n");
 		printf("Play again? [Y/n]: ");
 		fgets(input, sizeof(input), stdin);
 		if (input[0] == 'n' || input[0] == 'N') {
@@ -147,11 +318,6 @@ int main(int argc, char* argv[]) {
 		}
 	}
 
-	for (Player** pplayer = players; pplayer < players + playerCount; pplayer++) {
-		rust_freeplayer(*pplayer);
-	}
-	free(players);
-	rust_freeplayer(dealer);
-	rust_freedeck(deck);
+	rust_freetable(table);
 	return 0;
 }
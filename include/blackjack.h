@@ -15,6 +15,9 @@
 #ifndef BLACKJACK_H
 #define BLACKJACK_H
 
+#include <stdbool.h>
+#include <stdint.h>
+
 /**
  * Integer representations of card suits
  */
@@ -29,25 +32,148 @@ static const unsigned int SPADES   = 0b10000000;
 static const unsigned int SUIT     = 0b11110000;
 static const unsigned int VALUE    = 0b00001111;
 
+/**
+ * Dealer hole card variants
+ */
+static const unsigned int HOLE_CARD_PEEK = 0;
+static const unsigned int HOLE_CARD_NONE = 1;
+
+/**
+ * Error codes returned by fallible operations (0 on success)
+ */
+static const int BJ_OK                   = 0;
+static const int BJ_ERR_DECK_EXHAUSTED   = 1;
+static const int BJ_ERR_NO_ACTIVE_HAND   = 2;
+static const int BJ_ERR_ILLEGAL_ACTION   = 3;
+static const int BJ_ERR_WRONG_PHASE      = 4;
+static const int BJ_ERR_INVALID_SEAT     = 5;
+static const int BJ_ERR_INVALID_WAGER    = 6;
+static const int BJ_ERR_NO_BETS          = 7;
+static const int BJ_ERR_INSUFFICIENT_FUNDS = 8;
+static const int BJ_ERR_INVALID_CARD = 9;
+static const int BJ_ERR_TOO_MANY_SPOTS = 10;
+static const int BJ_ERR_INVALID_AMOUNT = 11;
+static const int BJ_ERR_BELOW_MINIMUM = 12;
+static const int BJ_ERR_ABOVE_MAXIMUM = 13;
+
+/**
+ * Double down restrictions
+ */
+static const unsigned int DOUBLE_ANY_TWO       = 0;
+static const unsigned int DOUBLE_NINE_TO_ELEVEN = 1;
+static const unsigned int DOUBLE_TEN_TO_ELEVEN  = 2;
+
+/**
+ * Pairs that may be split
+ */
+static const unsigned int SPLIT_SAME_RANK  = 0;
+static const unsigned int SPLIT_SAME_VALUE = 1;
+
+/**
+ * Card counting systems
+ */
+static const unsigned int COUNT_HI_LO       = 0;
+static const unsigned int COUNT_KO          = 1;
+static const unsigned int COUNT_HI_OPT_I    = 2;
+static const unsigned int COUNT_HI_OPT_II   = 3;
+static const unsigned int COUNT_OMEGA_II    = 4;
+static const unsigned int COUNT_ZEN         = 5;
+static const unsigned int COUNT_WONG_HALVES = 6;
+
+/**
+ * Surrender variants
+ */
+static const unsigned int SURRENDER_NONE  = 0;
+static const unsigned int SURRENDER_LATE  = 1;
+static const unsigned int SURRENDER_EARLY = 2;
+
+/**
+ * Blackjack payout ratios
+ */
+static const unsigned int PAYOUT_THREE_TO_TWO = 0;
+static const unsigned int PAYOUT_SIX_TO_FIVE  = 1;
+static const unsigned int PAYOUT_ONE_TO_ONE   = 2;
+
+/**
+ * Phases of a round at a table
+ */
+static const unsigned int PHASE_BETTING     = 0;
+static const unsigned int PHASE_DEALING     = 1;
+static const unsigned int PHASE_INSURANCE   = 2;
+static const unsigned int PHASE_PLAYER_TURN = 3;
+static const unsigned int PHASE_DEALER_TURN = 4;
+static const unsigned int PHASE_SETTLEMENT  = 5;
+static const unsigned int PHASE_EARLY_SURRENDER = 6;
+
+/**
+ * How the dealer's hand finished
+ */
+static const unsigned int DEALER_BUST      = 0;
+static const unsigned int DEALER_TOTAL     = 1;
+static const unsigned int DEALER_BLACKJACK = 2;
+static const unsigned int DEALER_HIDDEN    = 3;
+
+/**
+ * How a hand was settled
+ */
+static const unsigned int OUTCOME_WIN       = 0;
+static const unsigned int OUTCOME_LOSE      = 1;
+static const unsigned int OUTCOME_PUSH      = 2;
+static const unsigned int OUTCOME_BLACKJACK = 3;
+static const unsigned int OUTCOME_SURRENDER = 4;
+
+/**
+ * Player actions; legal action sets are reported as bitmasks with
+ * bit (1 << ACTION_*) set for each available action
+ */
+static const unsigned int ACTION_HIT       = 0;
+static const unsigned int ACTION_STAND     = 1;
+static const unsigned int ACTION_DOUBLE    = 2;
+static const unsigned int ACTION_SPLIT     = 3;
+static const unsigned int ACTION_SURRENDER = 4;
+static const unsigned int ACTION_INSURANCE = 5;
+
+/**
+ * An exact amount of money in cents. Amounts that can't be paid in whole
+ * cents (half of an odd wager, 3:2 on an odd wager) are rounded down, so the
+ * odd fraction stays with the house.
+ */
+typedef int64_t Money;
+
 typedef struct Player Player;
+typedef struct Dealer Dealer;
 typedef struct Hand Hand;
 typedef struct Deck Deck;
 typedef struct Card Card;
+typedef struct Rules Rules;
+typedef struct Table Table;
+typedef struct SettlementReport SettlementReport;
+typedef struct HandReport HandReport;
 
 extern void rust_freestr(char*);
 extern void rust_freeplayer(Player*);
+extern void rust_freedealer(Dealer*);
 extern void rust_freehand(Hand*);
 extern void rust_freecard(Card*);
 extern void rust_freedeck(Deck*);
+extern void rust_freerules(Rules*);
+extern void rust_freetable(Table*);
 
 /**
  * Create a new player
  * @param name Player name
- * @param isDealer Whether the player is the dealer
- * @param balance Player's initial balance
- * @return Newly constructed player
+ * @param balance Player's initial balance, in cents
+ * @return Newly constructed player, or NULL if the balance is negative or
+ * larger than the library supports
  */
-extern Player* player_new(char* name, int isDealer, int balance);
+extern Player* player_new(char* name, Money balance);
+
+/**
+ * Set the house rules a player is subject to
+ * @param player The player
+ * @param rules The rules to apply (copied)
+ */
+extern void player_setRules(Player* player, Rules* rules);
 
 /**
  * Get a player's name
@@ -61,40 +187,48 @@ extern char* player_getName(Player* player);
  * @param player The player
  * @return The player's current balance
  */
-extern int player_getBalance(Player* player);
+extern Money player_getBalance(Player* player);
 
 /**
  * Get a player's standing
  * @param player The player
  * @return The player's current standing
  */
-extern int player_getStanding(Player* player);
+extern Money player_getStanding(Player* player);
+
+/**
+ * Get the part of a player's balance not already wagered this round
+ * @param player The player
+ * @return The player's uncommitted balance
+ */
+extern Money player_getAvailableBalance(Player* player);
 
 /**
  * Determine whether a player is still playing
  * @param player The player
  * @return Whether the player's turn is still in progress
  */
-extern int player_isPlaying(Player* player);
+extern bool player_isPlaying(Player* player);
 
 /**
  * Hit
  * @param player The active player
  * @param deck The deck from which to deal
- * @return Whether the player busted
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
  */
 extern int player_hit(Player* player, Deck* deck);
 
 /**
  * Stand
  * @param player The active player
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
  */
-extern void player_stand(Player* player);
+extern int player_stand(Player* player);
 
 /**
  * Surrender the current hand
  * @param player The active player
- * @return Whether the current hand can be surrendered
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
  */
 extern int player_surrender(Player* player);
 
@@ -102,7 +236,7 @@ extern int player_surrender(Player* player);
  * Split the player's hand
  * @param player The active player
  * @param deck The deck from which to deal
- * @return Whether the hand can be split
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
  */
 extern int player_split(Player* player, Deck* deck);
 
@@ -110,38 +244,87 @@ extern int player_split(Player* player, Deck* deck);
  * Double down on the player's hand
  * @param player The active player
  * @param deck The deck from which to deal
- * @return Whether the player busted
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
  */
 extern int player_double(Player* player, Deck* deck);
 
 /**
  * Place a bet
  * @param player The player placing the bet
+ * @param wager Amount to wager, in cents
+ * @param deck The deck from which to deal
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int player_bet(Player* player, Money wager, Deck* deck);
+
+/**
+ * Determine whether a player may still place an insurance bet
+ * @param player The player to check
+ * @return Whether insurance or even money can be taken
+ */
+extern bool player_canInsure(Player* player);
+
+/**
+ * Place an insurance bet against a dealer Ace
+ * @param player The player taking insurance
+ * @param amount Insurance wager, at most half the original wager
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int player_insure(Player* player, Money amount);
+
+/**
+ * Take even money on a natural blackjack against a dealer Ace
+ * @param player The player holding the natural
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int player_takeEvenMoney(Player* player);
+
+/**
+ * Get the size of a player's insurance bet
+ * @param player The player to check
+ * @return The insurance wager, or 0 if none was placed
+ */
+extern Money player_getInsurance(Player* player);
+
+/**
+ * Get the actions available on a player's current hand. Insurance is
+ * never included; use table_legalActions while the dealer shows an Ace.
+ * @param player The player to check
+ * @return Bitmask of available ACTION_* values
+ */
+extern unsigned int player_legalActions(Player* player);
+
+/**
+ * Perform an action on a player's current hand. ACTION_INSURANCE is
+ * refused; insurance is taken through the table.
+ * @param player The active player
+ * @param action One of the ACTION_* constants
  * @param deck The deck from which to deal
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
  */
-extern void player_bet(Player* player, int, Deck* deck);
+extern int player_apply(Player* player, unsigned int action, Deck* deck);
 
 /**
  * Determine whether a player has already lost
  * @param player The player to check
  * @return Whether the player has surrendered or busted all their hands
  */
-extern int player_hasLost(Player* player);
+extern bool player_hasLost(Player* player);
 
 /**
  * Settle all bets and discard all cards drawn this round
  * @param player The player whose round is ending
- * @param dealerValue The value of the dealer's hand
+ * @param dealerOutcome One of the DEALER_* constants
+ * @param dealerTotal The value of the dealer's hand if they stood
  */
-extern void player_gameOver(Player* player, unsigned int dealerValue);
+extern void player_gameOver(Player* player, unsigned int dealerOutcome, unsigned int dealerTotal);
 
 /**
- * Play as the dealer
- * @param player The dealer
- * @param deck The deck from which to deal
- * @return The value of the dealer's hand, or 0 if the dealer busted
+ * Determine whether a player holds a natural blackjack
+ * @param player The player to check
+ * @return Whether the player's only hand is a natural blackjack
  */
-extern unsigned int player_playAsDealer(Player* player, Deck* deck);
+extern bool player_hasBlackjack(Player* player);
 
 /**
  * Get the number of hands a player has
@@ -163,14 +346,131 @@ extern Hand* player_getHandWithIndex(Player* player, unsigned int idx);
  * @param player The player to check
  * @return Whether the currently active hand can be surrendered
  */
-extern int player_canSurrenderCurrentHand(Player* player);
+extern bool player_canSurrenderCurrentHand(Player* player);
+
+/**
+ * Determine whether the hand on one of a player's spots can be surrendered
+ * @param player The player to check
+ * @param spot Spot index
+ * @return Whether that spot's hand can be surrendered
+ */
+extern bool player_canSurrenderSpot(Player* player, unsigned int spot);
 
 /**
  * Determine whether a player can split their currently active hand
  * @param player The player to check
  * @return Whether the currently active hand can be split
  */
-extern int player_canSplitHand(Player* player);
+extern bool player_canSplitHand(Player* player);
+
+/**
+ * Determine whether a player can double down on their currently active hand
+ * @param player The player to check
+ * @return Whether the currently active hand can be doubled
+ */
+extern bool player_canDoubleHand(Player* player);
+
+/**
+ * Create a new dealer with an empty house bankroll
+ * @param rules The house rules, which select the dealer's drawing policy (copied)
+ * @return Newly constructed dealer
+ */
+extern Dealer* dealer_new(Rules* rules);
+
+/**
+ * @param dealer The dealer
+ * @return The house bankroll, including everything won or lost so far
+ */
+extern Money dealer_getBankroll(Dealer* dealer);
+
+/**
+ * @param dealer The dealer
+ * @param bankroll The new house bankroll
+ * @return 0 on success, otherwise BJ_ERR_INVALID_AMOUNT
+ */
+extern int dealer_setBankroll(Dealer* dealer, Money bankroll);
+
+/**
+ * @param dealer The dealer
+ * @return Net amount the house has won (negative if lost) across all settled rounds
+ */
+extern Money dealer_getStanding(Dealer* dealer);
+
+/**
+ * Get a copy of the dealer's hand as the players see it. Until the hole
+ * card is turned over only the upcard is included.
+ * @param dealer The dealer
+ * @return The dealer's hand (must be freed by Rust), or NULL between rounds
+ */
+extern Hand* dealer_getHand(Dealer* dealer);
+
+/**
+ * Determine how the dealer's hand finished
+ * @param dealer The dealer
+ * @return One of the DEALER_* constants, DEALER_HIDDEN while the hole card
+ * is face down or between rounds
+ */
+extern unsigned int dealer_outcome(Dealer* dealer);
+
+/**
+ * @param dealer The dealer
+ * @return Whether the dealer has turned over a natural blackjack
+ */
+extern bool dealer_hasBlackjack(Dealer* dealer);
+
+/**
+ * Play out the dealer's hand according to the dealer's drawing policy
+ * @param dealer The dealer
+ * @param deck The deck from which to deal
+ * @return The value of the dealer's hand, or 0 if the dealer busted or the deck ran out
+ */
+extern unsigned int dealer_play(Dealer* dealer, Deck* deck);
+
+/**
+ * Deal the dealer's starting hand, with the second card face down
+ * (or not dealt at all at no-hole-card tables)
+ * @param dealer The dealer
+ * @param deck The deck from which to deal
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int dealer_deal(Dealer* dealer, Deck* deck);
+
+/**
+ * Get the dealer's face up card
+ * @param dealer The dealer
+ * @return The upcard (must be freed by Rust), or NULL if no cards have been dealt
+ */
+extern Card* dealer_getUpcard(Dealer* dealer);
+
+/**
+ * Get the dealer's hole card
+ * @param dealer The dealer
+ * @return The hole card (must be freed by Rust), or NULL if it is still face down
+ */
+extern Card* dealer_getHoleCard(Dealer* dealer);
+
+/**
+ * Determine whether the dealer's upcard allows insurance
+ * @param dealer The dealer
+ * @return Whether the dealer is showing an Ace
+ */
+extern bool dealer_offersInsurance(Dealer* dealer);
+
+/**
+ * Check the hole card for blackjack if the upcard is an Ace or ten-valued card
+ * @param dealer The dealer
+ * @param deck The deck the hand was dealt from, told about the hole card if it is revealed
+ * @return Whether the dealer has blackjack (the hole card is then revealed)
+ */
+extern bool dealer_peekForBlackjack(Dealer* dealer, Deck* deck);
+
+/**
+ * Turn over the dealer's hole card, dealing it first at no-hole-card tables
+ * @param dealer The dealer
+ * @param deck The deck from which to deal
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int dealer_revealHoleCard(Dealer* dealer, Deck* deck);
 
 /**
  * Get the number of cards in a hand
@@ -192,15 +492,67 @@ extern Card* hand_getCardWithIndex(Hand* hand, unsigned int idx);
  * @param hand Hand to check
  * @return Whether the hand is set
  */
-extern int hand_isSet(Hand* hand);
+extern bool hand_isSet(Hand* hand);
+
+/**
+ * Get the spot a hand was dealt to or split from
+ * @param hand Hand to check
+ * @return The spot index
+ */
+extern unsigned int hand_getSpot(Hand* hand);
+
+/**
+ * Check whether a hand is a natural blackjack
+ * @param hand Hand to check
+ * @return Whether the hand is 21 with its first two cards and did not come from a split
+ */
+extern bool hand_isBlackjack(Hand* hand);
 
 /**
  * Get the value of a hand
  * @param hand Hand whose value to check
- * @return Points the hand is worth
+ * @return The best total the hand can make
  */
 extern unsigned int hand_value(Hand* hand);
 
+/**
+ * @param hand Hand whose value to check
+ * @return The hand's total with every Ace counted as 1
+ */
+extern unsigned int hand_hardTotal(Hand* hand);
+
+/**
+ * @param hand Hand whose value to check
+ * @return The hand's total with one Ace counted as 11, or the hard total if
+ * that would go over 21 or the hand has no Ace
+ */
+extern unsigned int hand_softTotal(Hand* hand);
+
+/**
+ * @param hand Hand whose value to check
+ * @return Whether an Ace is counted as 11 in the hand's best total
+ */
+extern bool hand_isSoft(Hand* hand);
+
+/**
+ * @param hand Hand whose value to check
+ * @return Whether the hand is over 21
+ */
+extern bool hand_isBust(Hand* hand);
+
+/**
+ * @param hand Hand whose value to check
+ * @return Whether the hand is two cards of the same rank
+ */
+extern bool hand_isPair(Hand* hand);
+
+/**
+ * Describe the hand's totals, e.g. "7/17" for a soft hand or "17" for a hard one
+ * @param hand Hand whose value to describe
+ * @return String representation (must be freed by Rust)
+ */
+extern char* hand_valueToString(Hand* hand);
+
 /**
  * Obtain the string representation of a card
  * @param card Card to convert to string
@@ -215,6 +567,153 @@ extern char* card_toString(Card* card);
  */
 extern unsigned int card_toU32(Card* card);
 
+/**
+ * Obtain a card from its integer representation
+ * @param bits Integer representation of the card
+ * @return The card (must be freed by Rust), or NULL if the integer isn't a valid card
+ */
+extern Card* card_fromU32(unsigned int bits);
+
+/**
+ * Read a card in short ("AS", "10h") or long ("Ace of Spades") notation
+ * @param text The card's notation
+ * @return The card (must be freed by Rust), or NULL if the text isn't a valid card
+ */
+extern Card* card_fromString(const char* text);
+
+/**
+ * Format an amount of money with two decimal places, e.g. "12.50"
+ * @param amount The amount in cents
+ * @return String representation (must be freed by Rust)
+ */
+extern char* money_toString(Money amount);
+
+/**
+ * Parse an amount of money written in currency units, e.g. "10" or "12.5"
+ * @param text The amount as text
+ * @param amount Set to the parsed amount in cents on success
+ * @return 0 on success, otherwise BJ_ERR_INVALID_AMOUNT
+ */
+extern int money_fromString(const char* text, Money* amount);
+
+/**
+ * Create a new set of house rules with default settings
+ * @return Newly constructed rules
+ */
+extern Rules* rules_new();
+
+/**
+ * Set whether the dealer hits soft 17
+ * @param rules Rules to modify
+ * @param hits Whether the dealer draws to a soft 17
+ */
+extern void rules_setDealerHitsSoft17(Rules* rules, bool hits);
+
+/**
+ * Set how the dealer's hole card is handled
+ * @param rules Rules to modify
+ * @param rule One of the HOLE_CARD_* constants
+ */
+extern void rules_setHoleCard(Rules* rules, unsigned int rule);
+
+/**
+ * Set which hands may be doubled
+ * @param rules Rules to modify
+ * @param rule One of the DOUBLE_* constants
+ */
+extern void rules_setDouble(Rules* rules, unsigned int rule);
+
+/**
+ * Set which pairs may be split
+ * @param rules Rules to modify
+ * @param rule One of the SPLIT_* constants
+ */
+extern void rules_setSplit(Rules* rules, unsigned int rule);
+
+/**
+ * Set whether split hands may be doubled
+ * @param rules Rules to modify
+ * @param allowed Whether doubling after splitting is allowed
+ */
+extern void rules_setDoubleAfterSplit(Rules* rules, bool allowed);
+
+/**
+ * Set whether a player short of funds may double for less
+ * @param rules Rules to modify
+ * @param allowed Whether doubling for less is allowed
+ */
+extern void rules_setDoubleForLess(Rules* rules, bool allowed);
+
+/**
+ * Set the maximum number of hands a player may hold on one spot after splitting
+ * @param rules Rules to modify
+ * @param maxHands Maximum hand count
+ */
+extern void rules_setMaxHands(Rules* rules, unsigned int maxHands);
+
+/**
+ * Set the maximum number of spots a player may bet on in one round
+ * @param rules Rules to modify
+ * @param maxSpots Maximum spot count
+ */
+extern void rules_setMaxSpots(Rules* rules, unsigned int maxSpots);
+
+/**
+ * Set the smallest wager accepted on a spot
+ * @param rules Rules to modify
+ * @param min Table minimum, in cents
+ */
+extern void rules_setMinBet(Rules* rules, Money min);
+
+/**
+ * Set the largest total a player may wager across all their spots in one round
+ * @param rules Rules to modify
+ * @param max Table maximum in cents, or 0 for no limit
+ */
+extern void rules_setMaxBet(Rules* rules, Money max);
+
+/**
+ * Set the largest wager accepted on a single spot
+ * @param rules Rules to modify
+ * @param max Maximum per spot in cents, or 0 for no limit
+ */
+extern void rules_setMaxSpotBet(Rules* rules, Money max);
+
+/**
+ * Set whether hands from split aces may draw more than one card
+ * @param rules Rules to modify
+ * @param allowed Whether split aces may be hit
+ */
+extern void rules_setHitSplitAces(Rules* rules, bool allowed);
+
+/**
+ * Set whether a pair of aces formed after splitting aces may be split again
+ * @param rules Rules to modify
+ * @param allowed Whether aces may be resplit
+ */
+extern void rules_setResplitAces(Rules* rules, bool allowed);
+
+/**
+ * Set the surrender variant
+ * @param rules Rules to modify
+ * @param rule One of the SURRENDER_* constants
+ */
+extern void rules_setSurrender(Rules* rules, unsigned int rule);
+
+/**
+ * Set the payout for a natural blackjack
+ * @param rules Rules to modify
+ * @param payout One of the PAYOUT_* constants
+ */
+extern void rules_setBlackjackPayout(Rules* rules, unsigned int payout);
+
+/**
+ * Set whether hands resulting from a split may be surrendered
+ * @param rules Rules to modify
+ * @param allowed Whether surrendering split hands is allowed
+ */
+extern void rules_setSurrenderAfterSplit(Rules* rules, bool allowed);
+
 /**
  * Create a new set of decks
  * @param count Number of decks
@@ -222,6 +721,21 @@ extern unsigned int card_toU32(Card* card);
  */
 extern Deck* deck_new(unsigned int count);
 
+/**
+ * Create a new set of decks with reproducible shuffles
+ * @param count Number of decks
+ * @param seed Seed for the deck's random number generator
+ * @return Newly constructed Deck struct
+ */
+extern Deck* deck_newWithSeed(unsigned int count, uint64_t seed);
+
+/**
+ * Create a stacked deck that deals the given cards in order
+ * @param cards Whitespace separated cards in short notation, e.g. "AS KH 8D"
+ * @return Newly constructed Deck struct, or NULL if a card couldn't be read
+ */
+extern Deck* deck_fromString(const char* cards);
+
 /**
  * Shuffle a deck
  * @param deck Deck to shuffle
@@ -234,4 +748,449 @@ extern void deck_shuffle(Deck* deck);
  */
 extern void deck_reset(Deck* deck);
 
+/**
+ * Place the cut card after a fraction of the shoe
+ * @param deck Deck to modify
+ * @param penetration Fraction of the shoe dealt before reshuffling (0 to 1)
+ */
+extern void deck_setPenetration(Deck* deck, double penetration);
+
+/**
+ * Place the cut card at a given position in the shoe
+ * @param deck Deck to modify
+ * @param position Number of cards dealt before reshuffling
+ */
+extern void deck_setCutCard(Deck* deck, unsigned int position);
+
+/**
+ * Check whether the cut card has come out
+ * @param deck The deck
+ * @return Whether the deck should be shuffled before the next round
+ */
+extern bool deck_needsShuffle(Deck* deck);
+
+/**
+ * Get the number of cards left in the shoe
+ * @param deck The deck
+ * @return Number of undealt cards
+ */
+extern unsigned int deck_remaining(Deck* deck);
+
+/**
+ * Count the cards shown from now on. The count starts over whenever the shoe is shuffled.
+ * @param deck The deck
+ * @param system One of the COUNT_* constants
+ * @return Whether the counting system was recognized
+ */
+extern bool deck_attachCounter(Deck* deck, unsigned int system);
+
+/**
+ * Stop counting cards
+ * @param deck The deck
+ */
+extern void deck_detachCounter(Deck* deck);
+
+/**
+ * @param deck The deck
+ * @return Whether a counter is attached to the deck
+ */
+extern bool deck_hasCounter(Deck* deck);
+
+/**
+ * @param deck The deck
+ * @return The running count, or 0 if no counter is attached
+ */
+extern double deck_runningCount(Deck* deck);
+
+/**
+ * @param deck The deck
+ * @return The running count per remaining deck, or 0 if no counter is attached
+ */
+extern double deck_trueCount(Deck* deck);
+
+/**
+ * @param deck The deck
+ * @return Decks left to the nearest half deck, or 0 if no counter is attached
+ */
+extern double deck_remainingDecks(Deck* deck);
+
+/**
+ * @param deck The deck
+ * @return Number of cards counted since the shuffle, or 0 if no counter is attached
+ */
+extern unsigned int deck_cardsSeen(Deck* deck);
+
+/**
+ * Move all dealt cards to the discard pile at the end of a round
+ * @param deck The deck
+ */
+extern void deck_discard(Deck* deck);
+
+/**
+ * Create a new table
+ * @param deckCount Number of decks in the shoe
+ * @param rules House rules for the table (copied)
+ * @return Newly constructed table
+ */
+extern Table* table_new(unsigned int deckCount, Rules* rules);
+
+/**
+ * Create a new table dealing from an existing shoe
+ * @param deck The shoe (ownership passes to the table)
+ * @param rules House rules for the table (copied)
+ * @return Newly constructed table
+ */
+extern Table* table_newWithDeck(Deck* deck, Rules* rules);
+
+/**
+ * Check whether the shoe will be shuffled before the next deal
+ * @param table The table
+ * @return Whether the cut card has come out
+ */
+extern bool table_needsShuffle(Table* table);
+
+/**
+ * Seat a player at the table during the betting phase
+ * @param table The table
+ * @param player The player to seat (ownership passes to the table)
+ * @return The player's seat index, or -1 if players can't be seated now
+ */
+extern int table_addPlayer(Table* table, Player* player);
+
+/**
+ * Get the current phase of the round
+ * @param table The table
+ * @return One of the PHASE_* constants
+ */
+extern unsigned int table_getPhase(Table* table);
+
+/**
+ * Get the seat whose turn it is
+ * @param table The table
+ * @return The active seat index, or -1 outside of player turns
+ */
+extern int table_getActiveSeat(Table* table);
+
+/**
+ * Get the index of the hand being played
+ * @param table The table
+ * @return The active hand index, or -1 outside of player turns
+ */
+extern int table_getActiveHand(Table* table);
+
+/**
+ * Get the number of seated players
+ * @param table The table
+ * @return Number of seats in use
+ */
+extern unsigned int table_getSeatCount(Table* table);
+
+/**
+ * Get the player in a seat
+ * @param table The table
+ * @param seat Seat index
+ * @return The seated player (owned by the table, must not be freed), or NULL for an invalid seat
+ */
+extern Player* table_getSeat(Table* table, unsigned int seat);
+
+/**
+ * Get the table's dealer
+ * @param table The table
+ * @return The dealer (owned by the table, must not be freed)
+ */
+extern Dealer* table_getDealer(Table* table);
+
+/**
+ * Get the table's shoe
+ * @param table The table
+ * @return The shoe (owned by the table, must not be freed)
+ */
+extern Deck* table_getDeck(Table* table);
+
+/**
+ * Count the cards shown at the table. Hole cards are only counted once turned over.
+ * @param table The table
+ * @param system One of the COUNT_* constants
+ * @return Whether the counting system was recognized
+ */
+extern bool table_attachCounter(Table* table, unsigned int system);
+
+/**
+ * Set the amount of money the house starts with
+ * @param table The table
+ * @param bankroll The house bankroll
+ * @return 0 on success, otherwise BJ_ERR_INVALID_AMOUNT
+ */
+extern int table_setHouseBankroll(Table* table, Money bankroll);
+
+/**
+ * Get the wager placed in a seat this round
+ * @param table The table
+ * @param seat Seat index
+ * @return The wager, or 0 if none was placed or the seat is invalid
+ */
+extern Money table_getBet(Table* table, unsigned int seat);
+
+/**
+ * Place a wager for a seat during the betting phase, replacing any wagers
+ * already placed
+ * @param table The table
+ * @param seat Seat index
+ * @param wager Amount to wager
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_bet(Table* table, unsigned int seat, Money wager);
+
+/**
+ * Place a wager on an additional spot for a seat during the betting phase
+ * @param table The table
+ * @param seat Seat index
+ * @param wager Amount to wager on the new spot
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_addSpot(Table* table, unsigned int seat, Money wager);
+
+/**
+ * Get the number of spots a seat has bet on this round
+ * @param table The table
+ * @param seat Seat index
+ * @return Number of spots, or 0 for an invalid seat
+ */
+extern unsigned int table_getSpotCount(Table* table, unsigned int seat);
+
+/**
+ * Close betting once all wagers are placed
+ * @param table The table
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_closeBetting(Table* table);
+
+/**
+ * Deal the starting hands. If dealing fails, no hands are kept and the
+ * table returns to the betting phase.
+ * @param table The table
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_deal(Table* table);
+
+/**
+ * Place an insurance bet for a seat during the insurance phase
+ * @param table The table
+ * @param seat Seat index
+ * @param amount Insurance wager
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_insure(Table* table, unsigned int seat, Money amount);
+
+/**
+ * Place an insurance bet on one of a seat's spots during the insurance phase
+ * @param table The table
+ * @param seat Seat index
+ * @param spot Spot index
+ * @param amount Insurance wager
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_insureSpot(Table* table, unsigned int seat, unsigned int spot, Money amount);
+
+/**
+ * Take even money for a seat during the insurance phase
+ * @param table The table
+ * @param seat Seat index
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_takeEvenMoney(Table* table, unsigned int seat);
+
+/**
+ * Take even money on one of a seat's spots during the insurance phase
+ * @param table The table
+ * @param seat Seat index
+ * @param spot Spot index
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_takeEvenMoneySpot(Table* table, unsigned int seat, unsigned int spot);
+
+/**
+ * Surrender one of a seat's spots during the early surrender phase
+ * @param table The table
+ * @param seat Seat index
+ * @param spot Spot index
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_surrenderSpot(Table* table, unsigned int seat, unsigned int spot);
+
+/**
+ * Close the insurance phase and let the dealer check for blackjack, or offer
+ * early surrender first if the rules allow it
+ * @param table The table
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_closeInsurance(Table* table);
+
+/**
+ * Close the early surrender phase and let the dealer check for blackjack
+ * @param table The table
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_closeSurrender(Table* table);
+
+/**
+ * Get the actions available to a seat in the current phase
+ * @param table The table
+ * @param seat Seat index
+ * @return Bitmask of available ACTION_* values
+ */
+extern unsigned int table_legalActions(Table* table, unsigned int seat);
+
+/**
+ * Perform an action for a seat if it is legal in the current phase
+ * @param table The table
+ * @param seat Seat index
+ * @param action One of the ACTION_* constants
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_apply(Table* table, unsigned int seat, unsigned int action);
+
+/**
+ * Hit on the active hand
+ * @param table The table
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_hit(Table* table);
+
+/**
+ * Stand on the active hand
+ * @param table The table
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_stand(Table* table);
+
+/**
+ * Double down on the active hand
+ * @param table The table
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_double(Table* table);
+
+/**
+ * Split the active hand
+ * @param table The table
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_split(Table* table);
+
+/**
+ * Surrender the active hand
+ * @param table The table
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_surrender(Table* table);
+
+/**
+ * Play out the dealer's hand once all players have acted
+ * @param table The table
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_playDealer(Table* table);
+
+/**
+ * Call off the round in progress without settling it, for instance when the
+ * shoe runs out mid-round. Unsettled wagers are returned and betting reopens.
+ * @param table The table
+ */
+extern void table_voidRound(Table* table);
+
+/**
+ * Settle all bets and start the next round
+ * @param table The table
+ * @return 0 on success, otherwise one of the BJ_ERR_* codes
+ */
+extern int table_settle(Table* table);
+
+/**
+ * Get the settlement report for a seat from the most recently settled round
+ * @param table The table
+ * @param seat The seat index
+ * @return The seat's report (owned by the table, valid until the next settlement), or NULL
+ */
+extern SettlementReport* table_getSettlement(Table* table, unsigned int seat);
+
+/**
+ * Get the number of hands in a settlement report
+ * @param report The settlement report
+ * @return Number of hands the player held when the round was settled
+ */
+extern unsigned int settlement_handCount(SettlementReport* report);
+
+/**
+ * Get the report for one of the player's hands
+ * @param report The settlement report
+ * @param idx Index of the hand
+ * @return The hand's report (owned by the settlement report), or NULL
+ */
+extern HandReport* settlement_getHand(SettlementReport* report, unsigned int idx);
+
+/**
+ * Get the amount won or lost on insurance
+ * @param report The settlement report
+ * @return Insurance winnings, negative if the insurance bet was lost
+ */
+extern Money settlement_insurancePayout(SettlementReport* report);
+
+/**
+ * Get the total amount won or lost in the round
+ * @param report The settlement report
+ * @return Net winnings across all hands and insurance
+ */
+extern Money settlement_net(SettlementReport* report);
+
+/**
+ * Get the player's balance after settling
+ * @param report The settlement report
+ * @return The balance
+ */
+extern Money settlement_balance(SettlementReport* report);
+
+/**
+ * Get the number of cards in a settled hand
+ * @param hand The hand report
+ * @return Number of cards
+ */
+extern unsigned int handReport_cardCount(HandReport* hand);
+
+/**
+ * Get a card from a settled hand
+ * @param hand The hand report
+ * @param idx Index of the card
+ * @return The card (must be freed by Rust), or NULL for an invalid index
+ */
+extern Card* handReport_getCardWithIndex(HandReport* hand, unsigned int idx);
+
+/**
+ * Get the final value of a settled hand
+ * @param hand The hand report
+ * @return The hand's value
+ */
+extern unsigned int handReport_value(HandReport* hand);
+
+/**
+ * Get the total wager on a settled hand
+ * @param hand The hand report
+ * @return The wager, including any double
+ */
+extern Money handReport_wager(HandReport* hand);
+
+/**
+ * Get the outcome of a settled hand
+ * @param hand The hand report
+ * @return One of the OUTCOME_* constants
+ */
+extern unsigned int handReport_outcome(HandReport* hand);
+
+/**
+ * Get the amount won or lost on a settled hand
+ * @param hand The hand report
+ * @return Winnings, negative if the wager was lost
+ */
+extern Money handReport_payout(HandReport* hand);
+
 #endif
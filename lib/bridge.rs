@@ -12,10 +12,18 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub mod bridge {
 	use crate::player::player::*;
+	use crate::dealer::dealer::Dealer;
 	use crate::card::card::*;
-	use std::ffi::CString;
+	use crate::counting::counting::*;
+	use crate::error::error::BlackjackError;
+	use crate::money::money::Money;
+	use crate::outcome::outcome::*;
+	use crate::rules::rules::*;
+	use crate::table::table::*;
+	use std::ffi::{CStr, CString};
 	use std::os::raw::c_char;
 	use std::ptr;
 
@@ -51,6 +59,50 @@ pub mod bridge {
 		};
 	}
 
+	fn action_from_u32(action: u32) -> Option<Action> {
+		match action {
+			0 => Some(Action::Hit),
+			1 => Some(Action::Stand),
+			2 => Some(Action::Double),
+			3 => Some(Action::Split),
+			4 => Some(Action::Surrender),
+			5 => Some(Action::Insurance),
+			_ => None
+		}
+	}
+
+	fn counting_system_from_u32(system: u32) -> Option<Box<dyn CountingSystem>> {
+		match system {
+			0 => Some(Box::new(HiLo)),
+			1 => Some(Box::new(KnockOut)),
+			2 => Some(Box::new(HiOptI)),
+			3 => Some(Box::new(HiOptII)),
+			4 => Some(Box::new(OmegaII)),
+			5 => Some(Box::new(Zen)),
+			6 => Some(Box::new(WongHalves)),
+			_ => None
+		}
+	}
+
+	fn error_code<T>(result: Result<T, BlackjackError>) -> i32 {
+		match result {
+			Ok(_) => 0,
+			Err(err) => err.code()
+		}
+	}
+
+	/// Refuse amounts too large for the library to do arithmetic on
+	fn check_amount(amount: Money) -> Result<Money, BlackjackError> {
+		match amount.in_range() {
+			true => Ok(amount),
+			false => Err(BlackjackError::InvalidAmount)
+		}
+	}
+
+	fn action_mask(actions: Vec<Action>) -> u32 {
+		actions.iter().fold(0, |mask, &action| mask | 1 << (action as u32))
+	}
+
 	#[no_mangle]
 	pub extern "C" fn rust_freestr(ptr: *mut c_char) {
 		free_ptr!(CString, ptr);
@@ -61,6 +113,11 @@ pub mod bridge {
 		free_ptr!(Box, ptr);
 	}
 
+	#[no_mangle]
+	pub extern "C" fn rust_freedealer(ptr: *mut Dealer) {
+		free_ptr!(Box, ptr);
+	}
+
 	#[no_mangle]
 	pub extern "C" fn rust_freehand(ptr: *mut Hand) {
 		free_ptr!(Box, ptr);
@@ -77,16 +134,36 @@ pub mod bridge {
 	}
 
 	#[no_mangle]
-	pub extern "C" fn player_new(name: *mut c_char, is_dealer: bool, balance: i32) -> *mut Player {
+	pub extern "C" fn rust_freerules(ptr: *mut Rules) {
+		free_ptr!(Box, ptr);
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rust_freetable(ptr: *mut Table) {
+		free_ptr!(Box, ptr);
+	}
+
+	#[no_mangle]
+	pub extern "C" fn player_new(name: *mut c_char, balance: Money) -> *mut Player {
 		unsafe {
 			let rname = match CString::from_raw(name).into_string() {
 				Ok(converted) => converted,
 				Err(_) => "Unnamed player".to_string()
 			};
-			wrap!(Player::new(rname.to_string(), is_dealer, balance))
+			if balance.is_negative() || check_amount(balance).is_err() {
+				return ptr::null_mut();
+			}
+			wrap!(Player::new(rname.to_string(), balance))
 		}
 	}
 
+	#[no_mangle]
+	pub extern "C" fn player_setRules(ptr: *mut Player, prules: *const Rules) {
+		let player = unwrap_mut!(ptr);
+		let rules = unwrap!(prules);
+		player.set_rules(rules.clone())
+	}
+
 	#[no_mangle]
 	pub extern "C" fn player_getName(ptr: *const Player) -> *mut c_char {
 		let player = unwrap!(ptr);
@@ -97,17 +174,23 @@ pub mod bridge {
 	}
 
 	#[no_mangle]
-	pub extern "C" fn player_getBalance(ptr: *const Player) -> i32 {
+	pub extern "C" fn player_getBalance(ptr: *const Player) -> Money {
 		let player = unwrap!(ptr);
 		player.get_balance()
 	}
 
 	#[no_mangle]
-	pub extern "C" fn player_getStanding(ptr: *const Player) -> i32 {
+	pub extern "C" fn player_getStanding(ptr: *const Player) -> Money {
 		let player = unwrap!(ptr);
 		player.get_standing()
 	}
 
+	#[no_mangle]
+	pub extern "C" fn player_getAvailableBalance(ptr: *const Player) -> Money {
+		let player = unwrap!(ptr);
+		player.get_available_balance()
+	}
+
 	#[no_mangle]
 	pub extern "C" fn player_isPlaying(ptr: *const Player) -> bool {
 		let player = unwrap!(ptr);
@@ -115,43 +198,83 @@ pub mod bridge {
 	}
 
 	#[no_mangle]
-	pub extern "C" fn player_hit(ptr: *mut Player, pdeck: *mut Deck) -> bool {
+	pub extern "C" fn player_hit(ptr: *mut Player, pdeck: *mut Deck) -> i32 {
 		let player = unwrap_mut!(ptr);
-		let mut deck = unwrap_mut!(pdeck);
-		player.hit(&mut deck)
+		let deck = unwrap_mut!(pdeck);
+		error_code(player.hit(deck))
 	}
 
 	#[no_mangle]
-	pub extern "C" fn player_stand(ptr: *mut Player) {
+	pub extern "C" fn player_stand(ptr: *mut Player) -> i32 {
 		let player = unwrap_mut!(ptr);
-		player.stand()
+		error_code(player.stand())
 	}
 
 	#[no_mangle]
-	pub extern "C" fn player_surrender(ptr: *mut Player) -> bool {
+	pub extern "C" fn player_surrender(ptr: *mut Player) -> i32 {
 		let player = unwrap_mut!(ptr);
-		player.surrender()
+		error_code(player.surrender())
 	}
 
 	#[no_mangle]
-	pub extern "C" fn player_split(ptr: *mut Player, pdeck: *mut Deck) -> bool {
+	pub extern "C" fn player_split(ptr: *mut Player, pdeck: *mut Deck) -> i32 {
 		let player = unwrap_mut!(ptr);
-		let mut deck = unwrap_mut!(pdeck);
-		player.split(&mut deck)
+		let deck = unwrap_mut!(pdeck);
+		error_code(player.split(deck))
 	}
 
 	#[no_mangle]
-	pub extern "C" fn player_double(ptr: *mut Player, pdeck: *mut Deck) -> bool {
+	pub extern "C" fn player_double(ptr: *mut Player, pdeck: *mut Deck) -> i32 {
 		let player = unwrap_mut!(ptr);
-		let mut deck = unwrap_mut!(pdeck);
-		player.double(&mut deck)
+		let deck = unwrap_mut!(pdeck);
+		error_code(player.double(deck))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn player_bet(ptr: *mut Player, bet: Money, pdeck: *mut Deck) -> i32 {
+		let player = unwrap_mut!(ptr);
+		let deck = unwrap_mut!(pdeck);
+		error_code(player.bet(bet, deck))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn player_canInsure(ptr: *const Player) -> bool {
+		let player = unwrap!(ptr);
+		player.can_insure()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn player_insure(ptr: *mut Player, amount: Money) -> i32 {
+		let player = unwrap_mut!(ptr);
+		error_code(player.insure(amount))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn player_takeEvenMoney(ptr: *mut Player) -> i32 {
+		let player = unwrap_mut!(ptr);
+		error_code(player.take_even_money())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn player_getInsurance(ptr: *const Player) -> Money {
+		let player = unwrap!(ptr);
+		player.get_insurance()
 	}
 
 	#[no_mangle]
-	pub extern "C" fn player_bet(ptr: *mut Player, bet: i32, pdeck: *mut Deck) {
+	pub extern "C" fn player_legalActions(ptr: *const Player) -> u32 {
+		let player = unwrap!(ptr);
+		action_mask(player.legal_actions())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn player_apply(ptr: *mut Player, action: u32, pdeck: *mut Deck) -> i32 {
 		let player = unwrap_mut!(ptr);
-		let mut deck = unwrap_mut!(pdeck);
-		player.bet(bet, &mut deck)
+		let deck = unwrap_mut!(pdeck);
+		match action_from_u32(action) {
+			Some(action) => error_code(player.apply(action, deck)),
+			None => BlackjackError::IllegalAction(Action::Hit).code()
+		}
 	}
 
 	#[no_mangle]
@@ -161,16 +284,20 @@ pub mod bridge {
 	}
 
 	#[no_mangle]
-	pub extern "C" fn player_gameOver(ptr: *mut Player, dealer_value: u32) {
+	pub extern "C" fn player_gameOver(ptr: *mut Player, dealer_outcome: u32, dealer_total: u32) {
 		let player = unwrap_mut!(ptr);
-		player.game_over(dealer_value)
+		let dealer = match dealer_outcome {
+			0 => DealerOutcome::Bust,
+			2 => DealerOutcome::Blackjack,
+			_ => DealerOutcome::Total(dealer_total)
+		};
+		player.game_over(dealer);
 	}
 
 	#[no_mangle]
-	pub extern "C" fn player_playAsDealer(ptr: *mut Player, pdeck: *mut Deck) -> u32 {
-		let dealer = unwrap_mut!(ptr);
-		let mut deck = unwrap_mut!(pdeck);
-		dealer.play_as_dealer(&mut deck)
+	pub extern "C" fn player_hasBlackjack(ptr: *const Player) -> bool {
+		let player = unwrap!(ptr);
+		player.has_blackjack()
 	}
 
 	#[no_mangle]
@@ -191,12 +318,130 @@ pub mod bridge {
 		player.can_surrender_hand()
 	}
 
+	#[no_mangle]
+	pub extern "C" fn player_canSurrenderSpot(ptr: *const Player, spot: usize) -> bool {
+		let player = unwrap!(ptr);
+		player.can_surrender_spot(spot)
+	}
+
 	#[no_mangle]
 	pub extern "C" fn player_canSplitHand(ptr: *const Player) -> bool {
 		let player = unwrap!(ptr);
 		player.can_split_hand()
 	}
 
+	#[no_mangle]
+	pub extern "C" fn player_canDoubleHand(ptr: *const Player) -> bool {
+		let player = unwrap!(ptr);
+		player.can_double_hand()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn dealer_new(prules: *const Rules) -> *mut Dealer {
+		let rules = unwrap!(prules);
+		wrap!(Dealer::new(rules.clone()))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn dealer_getBankroll(ptr: *const Dealer) -> Money {
+		let dealer = unwrap!(ptr);
+		dealer.get_bankroll()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn dealer_setBankroll(ptr: *mut Dealer, bankroll: Money) -> i32 {
+		let dealer = unwrap_mut!(ptr);
+		error_code(check_amount(bankroll).map(|bankroll| dealer.set_bankroll(bankroll)))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn dealer_getStanding(ptr: *const Dealer) -> Money {
+		let dealer = unwrap!(ptr);
+		dealer.get_standing()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn dealer_getHand(ptr: *const Dealer) -> *mut Hand {
+		let dealer = unwrap!(ptr);
+		match dealer.get_hand() {
+			Some(hand) => wrap!(hand),
+			None => ptr::null_mut()
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn dealer_outcome(ptr: *const Dealer) -> u32 {
+		let dealer = unwrap!(ptr);
+		match dealer.outcome() {
+			Some(DealerOutcome::Bust) => 0,
+			Some(DealerOutcome::Total(_)) => 1,
+			Some(DealerOutcome::Blackjack) => 2,
+			None => 3
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn dealer_hasBlackjack(ptr: *const Dealer) -> bool {
+		let dealer = unwrap!(ptr);
+		dealer.has_blackjack()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn dealer_play(ptr: *mut Dealer, pdeck: *mut Deck) -> u32 {
+		let dealer = unwrap_mut!(ptr);
+		let deck = unwrap_mut!(pdeck);
+		match dealer.play(deck) {
+			Ok(DealerOutcome::Total(total)) => total,
+			Ok(DealerOutcome::Blackjack) => 21,
+			_ => 0
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn dealer_deal(ptr: *mut Dealer, pdeck: *mut Deck) -> i32 {
+		let dealer = unwrap_mut!(ptr);
+		let deck = unwrap_mut!(pdeck);
+		error_code(dealer.deal(deck))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn dealer_getUpcard(ptr: *const Dealer) -> *mut Card {
+		let dealer = unwrap!(ptr);
+		match dealer.get_upcard() {
+			Some(card) => wrap!(card),
+			None => ptr::null_mut()
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn dealer_getHoleCard(ptr: *const Dealer) -> *mut Card {
+		let dealer = unwrap!(ptr);
+		match dealer.get_hole_card() {
+			Some(card) => wrap!(card),
+			None => ptr::null_mut()
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn dealer_offersInsurance(ptr: *const Dealer) -> bool {
+		let dealer = unwrap!(ptr);
+		dealer.offers_insurance()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn dealer_peekForBlackjack(ptr: *mut Dealer, pdeck: *mut Deck) -> bool {
+		let dealer = unwrap_mut!(ptr);
+		let deck = unwrap_mut!(pdeck);
+		dealer.peek_for_blackjack(deck)
+	}
+
+	#[no_mangle]
+	pub extern "C" fn dealer_revealHoleCard(ptr: *mut Dealer, pdeck: *mut Deck) -> i32 {
+		let dealer = unwrap_mut!(ptr);
+		let deck = unwrap_mut!(pdeck);
+		error_code(dealer.reveal_hole_card(deck))
+	}
+
 	#[no_mangle]
 	pub extern "C" fn hand_cardCount(ptr: *const Hand) -> usize {
 		let hand = unwrap!(ptr);
@@ -215,10 +460,61 @@ pub mod bridge {
 		hand.get_is_set()
 	}
 
+	#[no_mangle]
+	pub extern "C" fn hand_getSpot(ptr: *const Hand) -> usize {
+		let hand = unwrap!(ptr);
+		hand.get_spot()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn hand_isBlackjack(ptr: *const Hand) -> bool {
+		let hand = unwrap!(ptr);
+		hand.is_blackjack()
+	}
+
 	#[no_mangle]
 	pub extern "C" fn hand_value(ptr: *const Hand) -> u32 {
 		let hand = unwrap!(ptr);
-		hand.value(false)
+		hand.value()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn hand_hardTotal(ptr: *const Hand) -> u32 {
+		let hand = unwrap!(ptr);
+		hand.get_value().hard
+	}
+
+	#[no_mangle]
+	pub extern "C" fn hand_softTotal(ptr: *const Hand) -> u32 {
+		let hand = unwrap!(ptr);
+		hand.get_value().soft
+	}
+
+	#[no_mangle]
+	pub extern "C" fn hand_isSoft(ptr: *const Hand) -> bool {
+		let hand = unwrap!(ptr);
+		hand.get_value().is_soft
+	}
+
+	#[no_mangle]
+	pub extern "C" fn hand_isBust(ptr: *const Hand) -> bool {
+		let hand = unwrap!(ptr);
+		hand.get_value().is_bust
+	}
+
+	#[no_mangle]
+	pub extern "C" fn hand_isPair(ptr: *const Hand) -> bool {
+		let hand = unwrap!(ptr);
+		hand.get_value().is_pair
+	}
+
+	#[no_mangle]
+	pub extern "C" fn hand_valueToString(ptr: *const Hand) -> *mut c_char {
+		let hand = unwrap!(ptr);
+		match CString::new(hand.get_value().to_string()) {
+			Ok(s) => s.into_raw(),
+			Err(_) => ptr::null_mut()
+		}
 	}
 
 	#[no_mangle]
@@ -236,11 +532,186 @@ pub mod bridge {
 		card.to_u32()
 	}
 
+	#[no_mangle]
+	pub extern "C" fn card_fromU32(bits: u32) -> *mut Card {
+		match Card::from_u32(bits) {
+			Ok(card) => wrap!(card),
+			Err(_) => ptr::null_mut()
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn card_fromString(text: *const c_char) -> *mut Card {
+		assert!(!text.is_null());
+		let text = unsafe { CStr::from_ptr(text) };
+		match text.to_str().map(str::parse::<Card>) {
+			Ok(Ok(card)) => wrap!(card),
+			_ => ptr::null_mut()
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn money_toString(amount: Money) -> *mut c_char {
+		match CString::new(amount.to_string()) {
+			Ok(s) => s.into_raw(),
+			Err(_) => ptr::null_mut()
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn money_fromString(text: *const c_char, out: *mut Money) -> i32 {
+		assert!(!text.is_null());
+		let amount = unwrap_mut!(out);
+		let text = unsafe { CStr::from_ptr(text) };
+		let parsed = match text.to_str() {
+			Ok(text) => text.parse::<Money>(),
+			Err(_) => Err(BlackjackError::InvalidAmount)
+		};
+		if let Ok(parsed) = parsed {
+			*amount = parsed;
+		}
+		error_code(parsed)
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_new() -> *mut Rules {
+		wrap!(Rules::new())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setDealerHitsSoft17(ptr: *mut Rules, hits: bool) {
+		let rules = unwrap_mut!(ptr);
+		rules.dealer_hits_soft_17 = hits;
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setHoleCard(ptr: *mut Rules, rule: u32) {
+		let rules = unwrap_mut!(ptr);
+		rules.hole_card = match rule {
+			1 => HoleCardRule::NoHoleCard,
+			_ => HoleCardRule::Peek
+		};
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setDouble(ptr: *mut Rules, rule: u32) {
+		let rules = unwrap_mut!(ptr);
+		rules.double = match rule {
+			1 => DoubleRule::NineToEleven,
+			2 => DoubleRule::TenToEleven,
+			_ => DoubleRule::AnyTwo
+		};
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setSplit(ptr: *mut Rules, rule: u32) {
+		let rules = unwrap_mut!(ptr);
+		rules.split = match rule {
+			1 => SplitRule::SameValue,
+			_ => SplitRule::SameRank
+		};
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setDoubleAfterSplit(ptr: *mut Rules, allowed: bool) {
+		let rules = unwrap_mut!(ptr);
+		rules.double_after_split = allowed;
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setDoubleForLess(ptr: *mut Rules, allowed: bool) {
+		let rules = unwrap_mut!(ptr);
+		rules.double_for_less = allowed;
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setMaxHands(ptr: *mut Rules, max_hands: usize) {
+		let rules = unwrap_mut!(ptr);
+		rules.max_hands = max_hands;
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setMaxSpots(ptr: *mut Rules, max_spots: usize) {
+		let rules = unwrap_mut!(ptr);
+		rules.max_spots = max_spots;
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setMinBet(ptr: *mut Rules, min: Money) {
+		let rules = unwrap_mut!(ptr);
+		rules.min_bet = min;
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setMaxBet(ptr: *mut Rules, max: Money) {
+		let rules = unwrap_mut!(ptr);
+		rules.max_bet = Some(max).filter(|max| max.is_positive());
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setMaxSpotBet(ptr: *mut Rules, max: Money) {
+		let rules = unwrap_mut!(ptr);
+		rules.max_spot_bet = Some(max).filter(|max| max.is_positive());
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setHitSplitAces(ptr: *mut Rules, allowed: bool) {
+		let rules = unwrap_mut!(ptr);
+		rules.hit_split_aces = allowed;
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setResplitAces(ptr: *mut Rules, allowed: bool) {
+		let rules = unwrap_mut!(ptr);
+		rules.resplit_aces = allowed;
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setSurrender(ptr: *mut Rules, rule: u32) {
+		let rules = unwrap_mut!(ptr);
+		rules.surrender = match rule {
+			1 => SurrenderRule::Late,
+			2 => SurrenderRule::Early,
+			_ => SurrenderRule::None
+		};
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setSurrenderAfterSplit(ptr: *mut Rules, allowed: bool) {
+		let rules = unwrap_mut!(ptr);
+		rules.surrender_after_split = allowed;
+	}
+
+	#[no_mangle]
+	pub extern "C" fn rules_setBlackjackPayout(ptr: *mut Rules, payout: u32) {
+		let rules = unwrap_mut!(ptr);
+		rules.blackjack_payout = match payout {
+			1 => BlackjackPayout::SixToFive,
+			2 => BlackjackPayout::OneToOne,
+			_ => BlackjackPayout::ThreeToTwo
+		};
+	}
+
 	#[no_mangle]
 	pub extern "C" fn deck_new(deck_count: usize) -> *mut Deck {
 		wrap!(Deck::new(deck_count))
 	}
 
+	#[no_mangle]
+	pub extern "C" fn deck_newWithSeed(deck_count: usize, seed: u64) -> *mut Deck {
+		wrap!(Deck::with_seed(deck_count, seed))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn deck_fromString(cards: *const c_char) -> *mut Deck {
+		assert!(!cards.is_null());
+		let notation = unsafe { CStr::from_ptr(cards) };
+		match notation.to_str().map(Deck::from_notation) {
+			Ok(Ok(deck)) => wrap!(deck),
+			_ => ptr::null_mut()
+		}
+	}
+
 	#[no_mangle]
 	pub extern "C" fn deck_shuffle(ptr: *mut Deck) {
 		let deck = unwrap_mut!(ptr);
@@ -256,6 +727,408 @@ pub mod bridge {
 	#[no_mangle]
 	pub extern "C" fn deck_getNextCard(ptr: *mut Deck) -> u32 {
 		let deck = unwrap_mut!(ptr);
-		deck.next_card().to_u32()
+		deck.next_card().map_or(0, |card| card.to_u32())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn deck_setPenetration(ptr: *mut Deck, penetration: f64) {
+		let deck = unwrap_mut!(ptr);
+		deck.set_penetration(penetration);
+	}
+
+	#[no_mangle]
+	pub extern "C" fn deck_setCutCard(ptr: *mut Deck, position: usize) {
+		let deck = unwrap_mut!(ptr);
+		deck.set_cut_card(position);
+	}
+
+	#[no_mangle]
+	pub extern "C" fn deck_needsShuffle(ptr: *const Deck) -> bool {
+		let deck = unwrap!(ptr);
+		deck.needs_shuffle()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn deck_remaining(ptr: *const Deck) -> usize {
+		let deck = unwrap!(ptr);
+		deck.remaining()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn deck_attachCounter(ptr: *mut Deck, system: u32) -> bool {
+		let deck = unwrap_mut!(ptr);
+		match counting_system_from_u32(system) {
+			Some(system) => {
+				deck.attach_counter(system);
+				true
+			},
+			None => false
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn deck_detachCounter(ptr: *mut Deck) {
+		let deck = unwrap_mut!(ptr);
+		deck.detach_counter();
+	}
+
+	#[no_mangle]
+	pub extern "C" fn deck_hasCounter(ptr: *const Deck) -> bool {
+		let deck = unwrap!(ptr);
+		deck.get_counter().is_some()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn deck_runningCount(ptr: *const Deck) -> f64 {
+		let deck = unwrap!(ptr);
+		deck.get_counter().map_or(0.0, |counter| counter.get_running_count())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn deck_trueCount(ptr: *const Deck) -> f64 {
+		let deck = unwrap!(ptr);
+		deck.get_counter().map_or(0.0, |counter| counter.get_true_count())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn deck_remainingDecks(ptr: *const Deck) -> f64 {
+		let deck = unwrap!(ptr);
+		deck.get_counter().map_or(0.0, |counter| counter.get_remaining_decks())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn deck_cardsSeen(ptr: *const Deck) -> usize {
+		let deck = unwrap!(ptr);
+		deck.get_counter().map_or(0, |counter| counter.get_cards_seen())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn deck_discard(ptr: *mut Deck) {
+		let deck = unwrap_mut!(ptr);
+		deck.discard();
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_new(deck_count: usize, prules: *const Rules) -> *mut Table {
+		let rules = unwrap!(prules);
+		wrap!(Table::new(deck_count, rules.clone()))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_newWithDeck(pdeck: *mut Deck, prules: *const Rules) -> *mut Table {
+		assert!(!pdeck.is_null());
+		let deck = unsafe { Box::from_raw(pdeck) };
+		let rules = unwrap!(prules);
+		wrap!(Table::with_deck(*deck, rules.clone()))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_needsShuffle(ptr: *const Table) -> bool {
+		let table = unwrap!(ptr);
+		table.needs_shuffle()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_addPlayer(ptr: *mut Table, pplayer: *mut Player) -> i32 {
+		let table = unwrap_mut!(ptr);
+		assert!(!pplayer.is_null());
+		let player = unsafe { Box::from_raw(pplayer) };
+		match table.add_player(*player) {
+			Ok(seat) => seat as i32,
+			Err(_) => -1
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_getPhase(ptr: *const Table) -> u32 {
+		let table = unwrap!(ptr);
+		match table.get_phase() {
+			Phase::Betting => 0,
+			Phase::Dealing => 1,
+			Phase::Insurance => 2,
+			Phase::PlayerTurn(_, _) => 3,
+			Phase::DealerTurn => 4,
+			Phase::Settlement => 5,
+			Phase::EarlySurrender => 6
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_getActiveSeat(ptr: *const Table) -> i32 {
+		let table = unwrap!(ptr);
+		match table.get_phase() {
+			Phase::PlayerTurn(seat, _) => seat as i32,
+			_ => -1
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_getActiveHand(ptr: *const Table) -> i32 {
+		let table = unwrap!(ptr);
+		match table.get_phase() {
+			Phase::PlayerTurn(_, hand) => hand as i32,
+			_ => -1
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_getSeatCount(ptr: *const Table) -> usize {
+		let table = unwrap!(ptr);
+		table.get_seat_count()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_getSeat(ptr: *const Table, seat: usize) -> *const Player {
+		let table = unwrap!(ptr);
+		table.get_seat(seat).map_or(ptr::null(), |player| player)
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_getDeck(ptr: *const Table) -> *const Deck {
+		let table = unwrap!(ptr);
+		table.get_deck()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_attachCounter(ptr: *mut Table, system: u32) -> bool {
+		let table = unwrap_mut!(ptr);
+		match counting_system_from_u32(system) {
+			Some(system) => {
+				table.attach_counter(system);
+				true
+			},
+			None => false
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_getDealer(ptr: *const Table) -> *const Dealer {
+		let table = unwrap!(ptr);
+		table.get_dealer()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_setHouseBankroll(ptr: *mut Table, bankroll: Money) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(check_amount(bankroll).map(|bankroll| table.set_house_bankroll(bankroll)))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_getBet(ptr: *const Table, seat: usize) -> Money {
+		let table = unwrap!(ptr);
+		table.get_bet(seat).unwrap_or(Money::ZERO)
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_bet(ptr: *mut Table, seat: usize, wager: Money) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.bet(seat, wager))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_addSpot(ptr: *mut Table, seat: usize, wager: Money) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.add_spot(seat, wager))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_getSpotCount(ptr: *const Table, seat: usize) -> usize {
+		let table = unwrap!(ptr);
+		table.get_spot_bets(seat).map_or(0, <[Money]>::len)
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_closeBetting(ptr: *mut Table) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.close_betting())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_deal(ptr: *mut Table) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.deal())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_insure(ptr: *mut Table, seat: usize, amount: Money) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.insure(seat, amount))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_insureSpot(ptr: *mut Table, seat: usize, spot: usize, amount: Money) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.insure_spot(seat, spot, amount))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_takeEvenMoney(ptr: *mut Table, seat: usize) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.take_even_money(seat))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_takeEvenMoneySpot(ptr: *mut Table, seat: usize, spot: usize) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.take_even_money_spot(seat, spot))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_surrenderSpot(ptr: *mut Table, seat: usize, spot: usize) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.surrender_spot(seat, spot))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_closeInsurance(ptr: *mut Table) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.close_insurance())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_closeSurrender(ptr: *mut Table) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.close_surrender())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_legalActions(ptr: *const Table, seat: usize) -> u32 {
+		let table = unwrap!(ptr);
+		action_mask(table.legal_actions(seat))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_apply(ptr: *mut Table, seat: usize, action: u32) -> i32 {
+		let table = unwrap_mut!(ptr);
+		match action_from_u32(action) {
+			Some(action) => error_code(table.apply(seat, action)),
+			None => BlackjackError::IllegalAction(Action::Hit).code()
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_hit(ptr: *mut Table) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.hit())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_stand(ptr: *mut Table) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.stand())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_double(ptr: *mut Table) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.double())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_split(ptr: *mut Table) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.split())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_surrender(ptr: *mut Table) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.surrender())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_playDealer(ptr: *mut Table) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.play_dealer())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_voidRound(ptr: *mut Table) {
+		let table = unwrap_mut!(ptr);
+		table.void_round()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_settle(ptr: *mut Table) -> i32 {
+		let table = unwrap_mut!(ptr);
+		error_code(table.settle())
+	}
+
+	#[no_mangle]
+	pub extern "C" fn table_getSettlement(ptr: *const Table, seat: usize) -> *const SettlementReport {
+		let table = unwrap!(ptr);
+		table.get_settlement().get(seat).map_or(ptr::null(), |report| report)
+	}
+
+	#[no_mangle]
+	pub extern "C" fn settlement_handCount(ptr: *const SettlementReport) -> usize {
+		let report = unwrap!(ptr);
+		report.hands.len()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn settlement_getHand(ptr: *const SettlementReport, idx: usize) -> *const HandReport {
+		let report = unwrap!(ptr);
+		report.hands.get(idx).map_or(ptr::null(), |hand| hand)
+	}
+
+	#[no_mangle]
+	pub extern "C" fn settlement_insurancePayout(ptr: *const SettlementReport) -> Money {
+		let report = unwrap!(ptr);
+		report.insurance_payout
+	}
+
+	#[no_mangle]
+	pub extern "C" fn settlement_net(ptr: *const SettlementReport) -> Money {
+		let report = unwrap!(ptr);
+		report.net()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn settlement_balance(ptr: *const SettlementReport) -> Money {
+		let report = unwrap!(ptr);
+		report.balance
+	}
+
+	#[no_mangle]
+	pub extern "C" fn handReport_cardCount(ptr: *const HandReport) -> usize {
+		let hand = unwrap!(ptr);
+		hand.cards.len()
+	}
+
+	#[no_mangle]
+	pub extern "C" fn handReport_getCardWithIndex(ptr: *const HandReport, idx: usize) -> *const Card {
+		let hand = unwrap!(ptr);
+		hand.cards.get(idx).map_or(ptr::null_mut(), |&card| wrap!(card))
+	}
+
+	#[no_mangle]
+	pub extern "C" fn handReport_value(ptr: *const HandReport) -> u32 {
+		let hand = unwrap!(ptr);
+		hand.value
+	}
+
+	#[no_mangle]
+	pub extern "C" fn handReport_wager(ptr: *const HandReport) -> Money {
+		let hand = unwrap!(ptr);
+		hand.wager
+	}
+
+	#[no_mangle]
+	pub extern "C" fn handReport_outcome(ptr: *const HandReport) -> u32 {
+		let hand = unwrap!(ptr);
+		match hand.outcome {
+			HandOutcome::Win => 0,
+			HandOutcome::Lose => 1,
+			HandOutcome::Push => 2,
+			HandOutcome::Blackjack => 3,
+			HandOutcome::Surrender => 4
+		}
+	}
+
+	#[no_mangle]
+	pub extern "C" fn handReport_payout(ptr: *const HandReport) -> Money {
+		let hand = unwrap!(ptr);
+		hand.payout
 	}
 }
@@ -14,32 +14,61 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 pub mod card {
-	use std::vec;
-	use rand::Rng;
+	use rand::{Rng, RngCore, SeedableRng};
+	use rand::rngs::StdRng;
+	use crate::counting::counting::*;
+	use crate::error::error::BlackjackError;
+	use std::convert::TryFrom;
+	use std::fmt;
+	use std::str::FromStr;
 
-	///This enum represents the 4 card types found in black jack.
-	#[derive(Copy, Clone, Debug)]
-	pub enum Symbol {
-		DIAMONDS,
-		HEARTS,
-		CLUBS,
-		SPADES
+	///This enum represents the 4 suits found in black jack.
+	#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	pub enum Suit {
+		Diamonds,
+		Hearts,
+		Clubs,
+		Spades
 	}
 
-	///This struct represents a card found in a black jack game. It contains a numeric value and
-	/// a symbol
-	#[derive(Debug, Copy, Clone)]
+	/// The rank of a card, from Ace (1) to King (13)
+	#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	pub enum Rank {
+		Ace = 1,
+		Two,
+		Three,
+		Four,
+		Five,
+		Six,
+		Seven,
+		Eight,
+		Nine,
+		Ten,
+		Jack,
+		Queen,
+		King
+	}
+
+	///This struct represents a card found in a black jack game. It contains a rank and
+	/// a suit
+	#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 	pub struct Card  {
-		pub value: u32,
-		pub symbol: Symbol
+		pub rank: Rank,
+		pub suit: Suit
 	}
 
-	#[no_mangle]
+	/// A shoe of one or more decks. Cards before `discard_index` have been
+	/// cleared from the table; cards between it and `card_index` are in play.
 	#[repr(C)]
 	pub struct Deck {
 		cards: Vec<Card>,
 		deck_count: usize,
-		card_index: usize
+		card_index: usize,
+		discard_index: usize,
+		cut_card: usize,
+		reshuffled_discards: bool,
+		rng: Box<dyn RngCore + Send>,
+		counter: Option<CardCounter>
 	}
 
 	static DIAMONDS: u32 = 0b0001_0000;
@@ -49,38 +78,169 @@ pub mod card {
 
 	impl Deck {
 		pub fn new(deck_count: usize) -> Deck {
+			Deck::with_rng(deck_count, StdRng::from_entropy())
+		}
+
+		/// Create a shoe whose shuffles are reproducible from the given seed
+		pub fn with_seed(deck_count: usize, seed: u64) -> Deck {
+			Deck::with_rng(deck_count, StdRng::seed_from_u64(seed))
+		}
+
+		/// Create a shoe that shuffles using the given random number generator
+		pub fn with_rng<R: RngCore + Send + 'static>(deck_count: usize, rng: R) -> Deck {
 			let mut cards: Vec<Card> = Vec::with_capacity(52 * deck_count);
 			for _ in 0..deck_count {
 				cards.append(&mut Deck::create_valid_deck());
 			}
-			Deck { cards, deck_count, card_index: 0 }
+			let cut_card = cards.len() * 3 / 4;
+			Deck {
+				cards,
+				deck_count,
+				card_index: 0,
+				discard_index: 0,
+				cut_card,
+				reshuffled_discards: false,
+				rng: Box::new(rng),
+				counter: None
+			}
+		}
+
+		/// Create a stacked shoe that deals the given cards in order. The cut
+		/// card is placed at the end so the order is kept until the shoe runs out.
+		pub fn from_cards(cards: Vec<Card>) -> Deck {
+			let mut deck = Deck::with_rng(0, StdRng::from_entropy());
+			deck.deck_count = cards.len().div_ceil(52);
+			deck.cut_card = cards.len();
+			deck.cards = cards;
+			deck
+		}
+
+		/// Create a stacked shoe from whitespace separated cards in short
+		/// notation, e.g. "AS KH 8D"
+		pub fn from_notation(notation: &str) -> Result<Deck, BlackjackError> {
+			let cards = notation.split_whitespace()
+				.map(str::parse)
+				.collect::<Result<Vec<Card>, BlackjackError>>()?;
+			Ok(Deck::from_cards(cards))
+		}
+
+		pub fn get_deck_count(&self) -> usize {
+			self.deck_count
+		}
+
+		/// Number of cards left in the shoe
+		pub fn remaining(&self) -> usize {
+			self.cards.len() - self.card_index
+		}
+
+		pub fn get_cut_card(&self) -> usize {
+			self.cut_card
+		}
+
+		/// Place the cut card at the given position in the shoe
+		pub fn set_cut_card(&mut self, position: usize) {
+			self.cut_card = position.min(self.cards.len());
+		}
+
+		/// Place the cut card after the given fraction of the shoe
+		pub fn set_penetration(&mut self, penetration: f64) {
+			let penetration = penetration.clamp(0.0, 1.0);
+			self.set_cut_card((self.cards.len() as f64 * penetration) as usize);
+		}
+
+		/// Whether the cut card has come out (or the discards had to be
+		/// reshuffled mid-round) and the shoe should be shuffled before
+		/// the next round
+		pub fn needs_shuffle(&self) -> bool {
+			self.card_index >= self.cut_card || self.reshuffled_discards
+		}
+
+		/// Move all cards dealt so far to the discard pile. Call this when
+		/// the cards are cleared from the table at the end of a round.
+		pub fn discard(&mut self) {
+			self.discard_index = self.card_index;
+		}
+
+		/// Count the cards shown from now on with the given system. The count
+		/// starts over whenever the shoe is shuffled.
+		pub fn attach_counter(&mut self, system: Box<dyn CountingSystem>) {
+			self.counter = Some(CardCounter::new(system, self.deck_count));
+		}
+
+		pub fn detach_counter(&mut self) -> Option<CardCounter> {
+			self.counter.take()
+		}
+
+		pub fn get_counter(&self) -> Option<&CardCounter> {
+			self.counter.as_ref()
+		}
+
+		/// Tell the attached counter, if any, that a card dealt face down has
+		/// been turned over
+		pub fn reveal(&mut self, card: &Card) {
+			if let Some(counter) = self.counter.as_mut() {
+				counter.observe(card);
+			}
 		}
 
 		pub fn create_valid_deck() -> Vec<Card> {
-			let sym = vec![Symbol::DIAMONDS, Symbol::HEARTS, Symbol::CLUBS, Symbol::SPADES];
-			(1..14).cycle().take(52).zip(sym.iter().cycle())
-				.fold(Vec::new(), |mut acc, x| {
-					acc.push(Card::new(x.0, x.1.clone()));
-					acc
-				})
+			Suit::ALL.iter()
+				.flat_map(|&suit| Rank::ALL.iter().map(move |&rank| Card::new(rank, suit)))
+				.collect()
 		}
 
 		pub fn shuffle(&mut self) {
-			for i in (1..self.deck_count * 52).rev() {
-				let idx: usize = rand::thread_rng().gen_range(0, i as usize);
-				let curr = i as usize;
-				let tmp = self.cards[curr];
-				self.cards[curr] = self.cards[idx];
-				self.cards[idx] = tmp;
-			}
+			Deck::shuffle_cards(&mut self.cards, &mut self.rng);
 			self.card_index = 0;
+			self.discard_index = 0;
+			self.reshuffled_discards = false;
+			if let Some(counter) = self.counter.as_mut() {
+				counter.reset();
+			}
+		}
+
+		/// Deal the next card face up. If the shoe runs out mid-round, the
+		/// discards are shuffled and placed behind the cards still in play.
+		pub fn next_card(&mut self) -> Result<Card, BlackjackError> {
+			let card = self.next_face_down_card()?;
+			self.reveal(&card);
+			Ok(card)
 		}
 
-		pub fn next_card(&mut self) -> Card {
+		/// Deal the next card without showing it to the attached counter.
+		/// Call `reveal` once the card is turned over.
+		pub fn next_face_down_card(&mut self) -> Result<Card, BlackjackError> {
+			if self.card_index >= self.cards.len() {
+				self.reshuffle_discards()?;
+			}
 			let card = self.cards[self.card_index];
 			self.card_index += 1;
-			card
+			Ok(card)
+		}
+
+		fn reshuffle_discards(&mut self) -> Result<(), BlackjackError> {
+			if self.discard_index == 0 {
+				return Err(BlackjackError::DeckExhausted);
+			}
+			let mut discards: Vec<Card> = self.cards.drain(..self.discard_index).collect();
+			Deck::shuffle_cards(&mut discards, &mut self.rng);
+			self.card_index = self.cards.len();
+			self.cards.append(&mut discards);
+			self.discard_index = 0;
+			self.reshuffled_discards = true;
+			// The discards are back in play, so the count no longer applies
+			if let Some(counter) = self.counter.as_mut() {
+				counter.reset();
+			}
+			Ok(())
+		}
 
+		/// Fisher-Yates shuffle; every permutation is equally likely
+		fn shuffle_cards(cards: &mut [Card], rng: &mut dyn RngCore) {
+			for i in (1..cards.len()).rev() {
+				let idx: usize = rng.gen_range(0..=i);
+				cards.swap(i, idx);
+			}
 		}
 
 		pub fn reset(&mut self) {
@@ -89,64 +249,213 @@ pub mod card {
 		}
 	}
 
-	impl Symbol {
+	impl Suit {
+		pub const ALL: [Suit; 4] = [Suit::Diamonds, Suit::Hearts, Suit::Clubs, Suit::Spades];
+
 		pub fn val(&self) -> u32 {
-			match &self {
-				Symbol::DIAMONDS => DIAMONDS,
-				Symbol::HEARTS => HEARTS,
-				Symbol::CLUBS => CLUBS,
-				Symbol::SPADES => SPADES
+			match self {
+				Suit::Diamonds => DIAMONDS,
+				Suit::Hearts => HEARTS,
+				Suit::Clubs => CLUBS,
+				Suit::Spades => SPADES
+			}
+		}
+
+		pub fn from_val(val: u32) -> Option<Suit> {
+			Suit::ALL.iter().copied().find(|suit| suit.val() == val)
+		}
+
+		pub fn name(&self) -> &'static str {
+			match self {
+				Suit::Diamonds => "Diamonds",
+				Suit::Hearts => "Hearts",
+				Suit::Clubs => "Clubs",
+				Suit::Spades => "Spades"
+			}
+		}
+
+		/// Single letter used in short card notation
+		pub fn short_name(&self) -> &'static str {
+			match self {
+				Suit::Diamonds => "D",
+				Suit::Hearts => "H",
+				Suit::Clubs => "C",
+				Suit::Spades => "S"
+			}
+		}
+	}
+
+	impl fmt::Display for Suit {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			write!(f, "{}", self.name())
+		}
+	}
+
+	impl FromStr for Suit {
+		type Err = BlackjackError;
+
+		/// Accepts either the name or the single letter of the suit
+		fn from_str(text: &str) -> Result<Suit, BlackjackError> {
+			let text = text.trim();
+			Suit::ALL.iter().copied()
+				.find(|suit| suit.name().eq_ignore_ascii_case(text) || suit.short_name().eq_ignore_ascii_case(text))
+				.ok_or(BlackjackError::InvalidCard)
+		}
+	}
+
+	impl Rank {
+		pub const ALL: [Rank; 13] = [
+			Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Seven,
+			Rank::Eight, Rank::Nine, Rank::Ten, Rank::Jack, Rank::Queen, Rank::King
+		];
+
+		/// Numeric value of the rank, from 1 for Ace to 13 for King
+		pub fn value(&self) -> u32 {
+			*self as u32
+		}
+
+		pub fn from_value(value: u32) -> Option<Rank> {
+			Rank::ALL.get((value as usize).wrapping_sub(1)).copied()
+		}
+
+		/// Points the rank is worth in a hand, counting an Ace as 11
+		pub fn score(&self) -> u32 {
+			match self {
+				Rank::Ace => 11,
+				Rank::Jack | Rank::Queen | Rank::King => 10,
+				rank => rank.value()
+			}
+		}
+
+		pub fn name(&self) -> &'static str {
+			match self {
+				Rank::Ace => "Ace",
+				Rank::Two => "Two",
+				Rank::Three => "Three",
+				Rank::Four => "Four",
+				Rank::Five => "Five",
+				Rank::Six => "Six",
+				Rank::Seven => "Seven",
+				Rank::Eight => "Eight",
+				Rank::Nine => "Nine",
+				Rank::Ten => "Ten",
+				Rank::Jack => "Jack",
+				Rank::Queen => "Queen",
+				Rank::King => "King"
+			}
+		}
+
+		/// Rank as written in short card notation
+		pub fn short_name(&self) -> &'static str {
+			match self {
+				Rank::Ace => "A",
+				Rank::Two => "2",
+				Rank::Three => "3",
+				Rank::Four => "4",
+				Rank::Five => "5",
+				Rank::Six => "6",
+				Rank::Seven => "7",
+				Rank::Eight => "8",
+				Rank::Nine => "9",
+				Rank::Ten => "10",
+				Rank::Jack => "J",
+				Rank::Queen => "Q",
+				Rank::King => "K"
 			}
 		}
+	}
+
+	impl fmt::Display for Rank {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			write!(f, "{}", self.name())
+		}
+	}
+
+	impl FromStr for Rank {
+		type Err = BlackjackError;
 
-		pub fn name(&self) -> &str {
-			match &self {
-				Symbol::DIAMONDS => "Diamonds",
-				Symbol::HEARTS => "Hearts",
-				Symbol::CLUBS => "Clubs",
-				Symbol::SPADES => "Spades"
+		/// Accepts the name of the rank or its short form (A, 2-10, T, J, Q, K)
+		fn from_str(text: &str) -> Result<Rank, BlackjackError> {
+			let text = text.trim();
+			if text.eq_ignore_ascii_case("T") {
+				return Ok(Rank::Ten);
 			}
+			Rank::ALL.iter().copied()
+				.find(|rank| rank.name().eq_ignore_ascii_case(text) || rank.short_name().eq_ignore_ascii_case(text))
+				.ok_or(BlackjackError::InvalidCard)
 		}
 	}
 
 	impl Card {
-		pub fn new(value: u32, symbol: Symbol) -> Card {
-			Card{value, symbol}
+		pub fn new(rank: Rank, suit: Suit) -> Card {
+			Card{rank, suit}
 		}
 
 		pub fn to_u32(&self) -> u32 {
-			self.symbol.val() | self.value
+			self.suit.val() | self.rank.value()
 		}
 
-		pub fn to_string(&self) -> String {
-			format!("{} of {}", self.value_to_string(), self.symbol.name())
+		/// Inverse of `to_u32`
+		pub fn from_u32(bits: u32) -> Result<Card, BlackjackError> {
+			let rank = Rank::from_value(bits & 0b1111);
+			let suit = Suit::from_val(bits & !0b1111);
+			match (rank, suit) {
+				(Some(rank), Some(suit)) => Ok(Card::new(rank, suit)),
+				_ => Err(BlackjackError::InvalidCard)
+			}
+		}
+
+		/// The card in short notation, e.g. "AS" or "10H"
+		pub fn short_name(&self) -> String {
+			format!("{}{}", self.rank.short_name(), self.suit.short_name())
 		}
 
 		pub fn score(&self) -> u32 {
-			match self.value {
-				1 => 11,
-				11..=13 => 10,
-				value => value
-			}
-		}
-
-		fn value_to_string(&self) -> &str {
-			match self.value {
-				1 => "Ace",
-				2 => "Two",
-				3 => "Three",
-				4 => "Four",
-				5 => "Five",
-				6 => "Six",
-				7 => "Seven",
-				8 => "Eight",
-				9 => "Nine",
-				10 => "Ten",
-				11 => "Jack",
-				12 => "Queen",
-				13 => "King",
-				_ => "Joker"
+			self.rank.score()
+		}
+	}
+
+	impl fmt::Display for Card {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			write!(f, "{} of {}", self.rank, self.suit)
+		}
+	}
+
+	impl FromStr for Card {
+		type Err = BlackjackError;
+
+		/// Accepts short notation ("AS", "10h") or the long form
+		/// ("Ace of Spades"), case insensitive
+		fn from_str(text: &str) -> Result<Card, BlackjackError> {
+			let text = text.trim();
+			let words: Vec<&str> = text.split_whitespace().collect();
+			if let [rank, of, suit] = words[..] {
+				if of.eq_ignore_ascii_case("of") {
+					return Ok(Card::new(rank.parse()?, suit.parse()?));
+				}
+				return Err(BlackjackError::InvalidCard);
+			}
+			match text.char_indices().last() {
+				Some((split, _)) if split > 0 => {
+					let (rank, suit) = text.split_at(split);
+					Ok(Card::new(rank.parse()?, suit.parse()?))
+				},
+				_ => Err(BlackjackError::InvalidCard)
 			}
 		}
 	}
+
+	impl TryFrom<u32> for Card {
+		type Error = BlackjackError;
+
+		fn try_from(bits: u32) -> Result<Card, BlackjackError> {
+			Card::from_u32(bits)
+		}
+	}
+
+	impl From<Card> for u32 {
+		fn from(card: Card) -> u32 {
+			card.to_u32()
+		}
+	}
 }
@@ -0,0 +1,242 @@
+// Copyright (C) 2019 Arc676/Alessandro Vinciguerra <alesvinciguerra@gmail.com>
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation (version 3).
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+pub mod counting {
+	use crate::card::card::{Card, Rank};
+
+	/// A card counting system, assigning each card a tag that is added to
+	/// the running count when the card is seen
+	pub trait CountingSystem: Send {
+		fn name(&self) -> &'static str;
+
+		/// Amount the card adds to the running count
+		fn tag(&self, card: &Card) -> f64;
+
+		/// Running count at the start of a freshly shuffled shoe. Balanced
+		/// systems start at 0.
+		fn initial_count(&self, _deck_count: usize) -> f64 {
+			0.0
+		}
+	}
+
+	/// Hi-Lo: 2-6 count +1, 10-A count -1
+	#[derive(Copy, Clone, Debug, Default)]
+	pub struct HiLo;
+
+	/// Knock-Out: like Hi-Lo but 7 also counts +1, making the system
+	/// unbalanced. The count starts at 4 - 4 × decks so the key count for
+	/// raising bets is independent of the number of decks.
+	#[derive(Copy, Clone, Debug, Default)]
+	pub struct KnockOut;
+
+	/// Hi-Opt I: 3-6 count +1, tens count -1, Aces are neutral
+	#[derive(Copy, Clone, Debug, Default)]
+	pub struct HiOptI;
+
+	/// Hi-Opt II: 2, 3, 6, 7 count +1, 4 and 5 count +2, tens count -2
+	#[derive(Copy, Clone, Debug, Default)]
+	pub struct HiOptII;
+
+	/// Omega II: 2, 3, 7 count +1, 4-6 count +2, 9 counts -1, tens count -2
+	#[derive(Copy, Clone, Debug, Default)]
+	pub struct OmegaII;
+
+	/// Zen: 2, 3, 7 count +1, 4-6 count +2, Aces count -1, tens count -2
+	#[derive(Copy, Clone, Debug, Default)]
+	pub struct Zen;
+
+	/// Wong Halves: a level-three count using half points
+	#[derive(Copy, Clone, Debug, Default)]
+	pub struct WongHalves;
+
+	impl CountingSystem for HiLo {
+		fn name(&self) -> &'static str {
+			"Hi-Lo"
+		}
+
+		fn tag(&self, card: &Card) -> f64 {
+			match card.score() {
+				2..=6 => 1.0,
+				7..=9 => 0.0,
+				_ => -1.0
+			}
+		}
+	}
+
+	impl CountingSystem for KnockOut {
+		fn name(&self) -> &'static str {
+			"KO"
+		}
+
+		fn tag(&self, card: &Card) -> f64 {
+			match card.score() {
+				2..=7 => 1.0,
+				8..=9 => 0.0,
+				_ => -1.0
+			}
+		}
+
+		fn initial_count(&self, deck_count: usize) -> f64 {
+			4.0 - 4.0 * deck_count as f64
+		}
+	}
+
+	impl CountingSystem for HiOptI {
+		fn name(&self) -> &'static str {
+			"Hi-Opt I"
+		}
+
+		fn tag(&self, card: &Card) -> f64 {
+			match card.score() {
+				3..=6 => 1.0,
+				10 => -1.0,
+				_ => 0.0
+			}
+		}
+	}
+
+	impl CountingSystem for HiOptII {
+		fn name(&self) -> &'static str {
+			"Hi-Opt II"
+		}
+
+		fn tag(&self, card: &Card) -> f64 {
+			match card.score() {
+				2 | 3 | 6 | 7 => 1.0,
+				4 | 5 => 2.0,
+				10 => -2.0,
+				_ => 0.0
+			}
+		}
+	}
+
+	impl CountingSystem for OmegaII {
+		fn name(&self) -> &'static str {
+			"Omega II"
+		}
+
+		fn tag(&self, card: &Card) -> f64 {
+			match card.score() {
+				2 | 3 | 7 => 1.0,
+				4..=6 => 2.0,
+				9 => -1.0,
+				10 => -2.0,
+				_ => 0.0
+			}
+		}
+	}
+
+	impl CountingSystem for Zen {
+		fn name(&self) -> &'static str {
+			"Zen"
+		}
+
+		fn tag(&self, card: &Card) -> f64 {
+			match card.score() {
+				2 | 3 | 7 => 1.0,
+				4..=6 => 2.0,
+				8 | 9 => 0.0,
+				10 => -2.0,
+				_ => -1.0
+			}
+		}
+	}
+
+	impl CountingSystem for WongHalves {
+		fn name(&self) -> &'static str {
+			"Wong Halves"
+		}
+
+		fn tag(&self, card: &Card) -> f64 {
+			match card.score() {
+				2 | 7 => 0.5,
+				3 | 4 | 6 => 1.0,
+				5 => 1.5,
+				8 => 0.0,
+				9 => -0.5,
+				_ => -1.0
+			}
+		}
+	}
+
+	/// Keeps the count of the cards seen since the shoe was last shuffled
+	pub struct CardCounter {
+		system: Box<dyn CountingSystem>,
+		deck_count: usize,
+		running_count: f64,
+		cards_seen: usize,
+		seen_by_rank: [usize; 13]
+	}
+
+	impl CardCounter {
+		pub fn new(system: Box<dyn CountingSystem>, deck_count: usize) -> CardCounter {
+			let mut counter = CardCounter {
+				system, deck_count, running_count: 0.0, cards_seen: 0, seen_by_rank: [0; 13]
+			};
+			counter.reset();
+			counter
+		}
+
+		pub fn get_system_name(&self) -> &'static str {
+			self.system.name()
+		}
+
+		/// Count a card that has been shown to the players
+		pub fn observe(&mut self, card: &Card) {
+			self.running_count += self.system.tag(card);
+			self.cards_seen += 1;
+			self.seen_by_rank[card.rank.value() as usize - 1] += 1;
+		}
+
+		/// Start counting a freshly shuffled shoe
+		pub fn reset(&mut self) {
+			self.running_count = self.system.initial_count(self.deck_count);
+			self.cards_seen = 0;
+			self.seen_by_rank = [0; 13];
+		}
+
+		pub fn get_running_count(&self) -> f64 {
+			self.running_count
+		}
+
+		pub fn get_cards_seen(&self) -> usize {
+			self.cards_seen
+		}
+
+		/// Number of cards of the given rank seen since the shuffle
+		pub fn get_seen(&self, rank: Rank) -> usize {
+			self.seen_by_rank[rank.value() as usize - 1]
+		}
+
+		/// Fraction of the shoe seen since the shuffle
+		pub fn get_penetration(&self) -> f64 {
+			match self.deck_count {
+				0 => 0.0,
+				decks => (self.cards_seen as f64 / (decks * 52) as f64).min(1.0)
+			}
+		}
+
+		/// Decks left in the shoe, estimated to the nearest half deck the way
+		/// a player eyeing the discard tray would. Never less than half a deck.
+		pub fn get_remaining_decks(&self) -> f64 {
+			let remaining = self.deck_count as f64 * (1.0 - self.get_penetration());
+			((remaining * 2.0).round() / 2.0).max(0.5)
+		}
+
+		/// Running count per remaining deck
+		pub fn get_true_count(&self) -> f64 {
+			self.running_count / self.get_remaining_decks()
+		}
+	}
+}
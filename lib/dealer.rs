@@ -0,0 +1,207 @@
+// Copyright (C) 2019 Arc676/Alessandro Vinciguerra <alesvinciguerra@gmail.com>
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation (version 3).
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+pub mod dealer {
+	use crate::card::card::*;
+	use crate::error::error::BlackjackError;
+	use crate::money::money::Money;
+	use crate::outcome::outcome::*;
+	use crate::player::player::Hand;
+	use crate::rules::rules::*;
+
+	/// The dealer's side of the table: a single hand played by a fixed
+	/// drawing policy and the house bankroll that pays out to the players
+	pub struct Dealer {
+		hand: Option<Hand>,
+		hole_card_revealed: bool,
+		policy: Box<dyn DealerPolicy>,
+		bankroll: Money,
+		standing: Money,
+		rules: Rules
+	}
+
+	impl Dealer {
+		/// Create a dealer with an empty house bankroll, drawing according
+		/// to the policy selected by the given rules
+		pub fn new(rules: Rules) -> Dealer {
+			Dealer::with_bankroll(rules, Money::ZERO)
+		}
+
+		pub fn with_bankroll(rules: Rules, bankroll: Money) -> Dealer {
+			Dealer {
+				hand: None, hole_card_revealed: false, policy: rules.dealer_policy(),
+				bankroll, standing: Money::ZERO, rules
+			}
+		}
+
+		pub fn get_rules(&self) -> &Rules {
+			&self.rules
+		}
+
+		/// Replace the rules, resetting the drawing policy to the one they select
+		pub fn set_rules(&mut self, rules: Rules) {
+			self.policy = rules.dealer_policy();
+			self.rules = rules;
+		}
+
+		/// Replace the dealer's drawing policy
+		pub fn set_policy(&mut self, policy: Box<dyn DealerPolicy>) {
+			self.policy = policy;
+		}
+
+		/// The house's money, including everything won or lost so far
+		pub fn get_bankroll(&self) -> Money {
+			self.bankroll
+		}
+
+		pub fn set_bankroll(&mut self, bankroll: Money) {
+			self.bankroll = bankroll;
+		}
+
+		/// Net amount the house has won (or, if negative, lost) across all
+		/// settled rounds
+		pub fn get_standing(&self) -> Money {
+			self.standing
+		}
+
+		/// The dealer's hand as the players see it: only the upcard until the
+		/// hole card is turned over
+		pub fn get_hand(&self) -> Option<Hand> {
+			self.hand.as_ref().map(|hand| {
+				if self.hole_card_revealed { hand.clone() } else { hand.truncated(1) }
+			})
+		}
+
+		/// Deal the dealer's starting hand. The upcard is dealt face up and,
+		/// unless the table plays without a hole card, a second card is dealt
+		/// face down.
+		pub fn deal(&mut self, deck: &mut Deck) -> Result<(), BlackjackError> {
+			let mut hand = Hand::deal(Money::ZERO, deck, 1)?;
+			if self.rules.hole_card == HoleCardRule::Peek {
+				hand.add_card(deck.next_face_down_card()?);
+			}
+			self.hand = Some(hand);
+			self.hole_card_revealed = false;
+			Ok(())
+		}
+
+		pub fn get_upcard(&self) -> Option<Card> {
+			self.hand.as_ref().map(|hand| hand.get_card_at(0))
+		}
+
+		/// The dealer's second card, if it has been dealt and turned over
+		pub fn get_hole_card(&self) -> Option<Card> {
+			match (&self.hand, self.hole_card_revealed) {
+				(Some(hand), true) if hand.get_card_count() > 1 => Some(hand.get_card_at(1)),
+				_ => None
+			}
+		}
+
+		pub fn hole_card_revealed(&self) -> bool {
+			self.hole_card_revealed
+		}
+
+		/// Whether the dealer has turned over a natural blackjack
+		pub fn has_blackjack(&self) -> bool {
+			self.hole_card_revealed && self.hand_is_blackjack()
+		}
+
+		fn hand_is_blackjack(&self) -> bool {
+			self.hand.as_ref().is_some_and(|hand| hand.is_blackjack())
+		}
+
+		pub fn has_busted(&self) -> bool {
+			self.hand.as_ref().is_some_and(|hand| hand.busted())
+		}
+
+		/// Whether the dealer's upcard allows players to take insurance
+		pub fn offers_insurance(&self) -> bool {
+			self.get_upcard().is_some_and(|card| card.rank == Rank::Ace)
+		}
+
+		/// Whether the dealer's upcard calls for checking the hole card for blackjack
+		pub fn should_peek(&self) -> bool {
+			self.rules.hole_card == HoleCardRule::Peek &&
+				self.get_upcard().is_some_and(|card| card.score() >= 10)
+		}
+
+		/// Check the hole card for blackjack without showing it to the players.
+		/// If the dealer has blackjack the hole card is turned over.
+		pub fn peek_for_blackjack(&mut self, deck: &mut Deck) -> bool {
+			if !self.should_peek() || !self.hand_is_blackjack() {
+				return false;
+			}
+			if let Some(hand) = self.hand.as_mut() {
+				hand.set();
+				deck.reveal(&hand.get_card_at(1));
+			}
+			self.hole_card_revealed = true;
+			true
+		}
+
+		/// Turn over the hole card, dealing it first at no-hole-card tables
+		pub fn reveal_hole_card(&mut self, deck: &mut Deck) -> Result<(), BlackjackError> {
+			if self.hole_card_revealed {
+				return Ok(());
+			}
+			let hand = self.hand.as_mut().ok_or(BlackjackError::NoActiveHand)?;
+			if hand.get_card_count() < 2 {
+				hand.hit(deck)?;
+			} else {
+				deck.reveal(&hand.get_card_at(1));
+			}
+			self.hole_card_revealed = true;
+			Ok(())
+		}
+
+		/// Play out the dealer's hand, drawing for as long as the dealer's
+		/// policy says to
+		pub fn play(&mut self, deck: &mut Deck) -> Result<DealerOutcome, BlackjackError> {
+			self.reveal_hole_card(deck)?;
+			let hand = self.hand.as_mut().ok_or(BlackjackError::NoActiveHand)?;
+			while !hand.busted() && self.policy.hits(hand.value(), hand.is_soft()) {
+				hand.hit(deck)?;
+			}
+			hand.set();
+			self.outcome().ok_or(BlackjackError::NoActiveHand)
+		}
+
+		/// How the dealer's hand finished. Only meaningful once the dealer has
+		/// played or found blackjack when peeking; `None` while the hole card
+		/// is still face down.
+		pub fn outcome(&self) -> Option<DealerOutcome> {
+			match &self.hand {
+				Some(_) if !self.hole_card_revealed => None,
+				Some(hand) if hand.is_blackjack() => Some(DealerOutcome::Blackjack),
+				Some(hand) if hand.busted() => Some(DealerOutcome::Bust),
+				Some(hand) => Some(DealerOutcome::Total(hand.value())),
+				None => None
+			}
+		}
+
+		/// Collect losing bets and pay out winning ones from the house
+		/// bankroll, then clear the dealer's hand
+		pub fn game_over(&mut self, reports: &[SettlementReport]) {
+			let house: Money = reports.iter().map(|report| -report.net()).sum();
+			self.bankroll += house;
+			self.standing += house;
+			self.clear_hand();
+		}
+
+		pub(crate) fn clear_hand(&mut self) {
+			self.hand = None;
+			self.hole_card_revealed = false;
+		}
+	}
+}
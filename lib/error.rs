@@ -0,0 +1,95 @@
+// Copyright (C) 2019 Arc676/Alessandro Vinciguerra <alesvinciguerra@gmail.com>
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation (version 3).
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+pub mod error {
+	use std::error::Error;
+	use std::fmt;
+	use crate::money::money::Money;
+	use crate::player::player::Action;
+	use crate::table::table::Phase;
+
+	/// Reasons a game operation can be refused
+	#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+	pub enum BlackjackError {
+		/// The shoe has no cards left to deal
+		DeckExhausted,
+		/// The player has no hand left to play
+		NoActiveHand,
+		/// The action is not allowed on the current hand
+		IllegalAction(Action),
+		/// The table is not in a phase where this call is allowed
+		WrongPhase(Phase),
+		/// There is no player in the given seat
+		InvalidSeat(usize),
+		/// The wager is not a positive amount within the allowed range
+		InvalidWager(Money),
+		/// Cards can't be dealt because nobody has placed a bet
+		NoBets,
+		/// The player's balance doesn't cover the additional wager
+		InsufficientFunds,
+		/// The text doesn't describe a card
+		InvalidCard,
+		/// The player already has a bet on as many spots as the table allows
+		TooManySpots,
+		/// The text doesn't describe an amount of money
+		InvalidAmount,
+		/// The wager is less than the table minimum, given here
+		BelowTableMinimum(Money),
+		/// The wager exceeds the given table maximum
+		AboveTableMaximum(Money)
+	}
+
+	impl BlackjackError {
+		/// Nonzero integer representation of the error for the C bridge
+		pub fn code(&self) -> i32 {
+			match self {
+				BlackjackError::DeckExhausted => 1,
+				BlackjackError::NoActiveHand => 2,
+				BlackjackError::IllegalAction(_) => 3,
+				BlackjackError::WrongPhase(_) => 4,
+				BlackjackError::InvalidSeat(_) => 5,
+				BlackjackError::InvalidWager(_) => 6,
+				BlackjackError::NoBets => 7,
+				BlackjackError::InsufficientFunds => 8,
+				BlackjackError::InvalidCard => 9,
+				BlackjackError::TooManySpots => 10,
+				BlackjackError::InvalidAmount => 11,
+				BlackjackError::BelowTableMinimum(_) => 12,
+				BlackjackError::AboveTableMaximum(_) => 13
+			}
+		}
+	}
+
+	impl fmt::Display for BlackjackError {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			match self {
+				BlackjackError::DeckExhausted => write!(f, "The deck has run out of cards"),
+				BlackjackError::NoActiveHand => write!(f, "There is no hand left to play"),
+				BlackjackError::IllegalAction(action) => write!(f, "Can't {} this hand now", action.name()),
+				BlackjackError::WrongPhase(phase) => write!(f, "Not allowed during {:?}", phase),
+				BlackjackError::InvalidSeat(seat) => write!(f, "There is no player in seat {}", seat),
+				BlackjackError::InvalidWager(wager) => write!(f, "Invalid wager: {}", wager),
+				BlackjackError::NoBets => write!(f, "No bets have been placed"),
+				BlackjackError::InsufficientFunds => write!(f, "Insufficient funds"),
+				BlackjackError::InvalidCard => write!(f, "Not a valid card"),
+				BlackjackError::TooManySpots => write!(f, "No more spots are available"),
+				BlackjackError::InvalidAmount => write!(f, "Not a valid amount of money"),
+				BlackjackError::BelowTableMinimum(min) => write!(f, "The table minimum is {}", min),
+				BlackjackError::AboveTableMaximum(max) => write!(f, "The table maximum is {}", max)
+			}
+		}
+	}
+
+	impl Error for BlackjackError {}
+}
@@ -12,6 +12,15 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+#![allow(clippy::module_inception)]
+
 pub mod card;
+pub mod counting;
+pub mod dealer;
+pub mod error;
+pub mod money;
+pub mod outcome;
 pub mod player;
+pub mod rules;
+pub mod table;
 pub mod bridge;
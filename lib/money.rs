@@ -0,0 +1,214 @@
+// Copyright (C) 2019 Arc676/Alessandro Vinciguerra <alesvinciguerra@gmail.com>
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation (version 3).
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+pub mod money {
+	use std::convert::TryFrom;
+	use std::fmt;
+	use std::iter::Sum;
+	use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+	use std::str::FromStr;
+	use crate::error::error::BlackjackError;
+
+	/// An exact amount of money, counted in cents.
+	///
+	/// The arithmetic operators panic on overflow instead of wrapping; the
+	/// `checked_*` methods report it instead.
+	///
+	/// Rounding policy: an amount that can't be paid out in whole cents,
+	/// such as half of an odd wager or 3:2 on an odd wager, is rounded down
+	/// to the cent, so the odd fraction always stays with the house. A
+	/// surrendered 25 cent wager returns 12 cents and forfeits 13; 3:2 on a
+	/// 25 cent wager pays 37 cents.
+	#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	#[repr(transparent)]
+	pub struct Money(i64);
+
+	impl Money {
+		pub const ZERO: Money = Money(0);
+
+		/// The largest balance or bankroll the library works with. Bets that
+		/// could win a player more than this are refused, which keeps every
+		/// sum of balances and payouts well clear of overflow.
+		pub const MAX: Money = Money::from_units(100_000_000_000);
+
+		pub const fn from_cents(cents: i64) -> Money {
+			Money(cents)
+		}
+
+		/// An amount in whole currency units, i.e. hundreds of cents
+		pub const fn from_units(units: i64) -> Money {
+			match Money::checked_from_units(units) {
+				Some(amount) => amount,
+				None => panic!("Money overflow")
+			}
+		}
+
+		pub const fn checked_from_units(units: i64) -> Option<Money> {
+			match units.checked_mul(100) {
+				Some(cents) => Some(Money(cents)),
+				None => None
+			}
+		}
+
+		pub fn cents(&self) -> i64 {
+			self.0
+		}
+
+		pub fn is_positive(&self) -> bool {
+			self.0 > 0
+		}
+
+		pub fn is_negative(&self) -> bool {
+			self.0 < 0
+		}
+
+		/// Whether the amount is no further from zero than `Money::MAX`
+		pub fn in_range(&self) -> bool {
+			-Money::MAX <= *self && *self <= Money::MAX
+		}
+
+		pub fn checked_add(self, other: Money) -> Option<Money> {
+			self.0.checked_add(other.0).map(Money)
+		}
+
+		pub fn checked_sub(self, other: Money) -> Option<Money> {
+			self.0.checked_sub(other.0).map(Money)
+		}
+
+		pub fn checked_mul(self, factor: i64) -> Option<Money> {
+			self.0.checked_mul(factor).map(Money)
+		}
+
+		/// The given fraction of this amount, rounded down to the cent
+		pub fn checked_ratio(self, numerator: i64, denominator: i64) -> Option<Money> {
+			if denominator == 0 {
+				return None;
+			}
+			let cents = (self.0 as i128 * numerator as i128).div_euclid(denominator as i128);
+			i64::try_from(cents).ok().map(Money)
+		}
+
+		/// The given fraction of this amount, rounded down to the cent
+		pub fn ratio(self, numerator: i64, denominator: i64) -> Money {
+			self.checked_ratio(numerator, denominator).expect("Money overflow")
+		}
+
+		/// Half of this amount, rounded down to the cent
+		pub fn half(self) -> Money {
+			self.ratio(1, 2)
+		}
+	}
+
+	impl Add for Money {
+		type Output = Money;
+
+		fn add(self, other: Money) -> Money {
+			self.checked_add(other).expect("Money overflow")
+		}
+	}
+
+	impl Sub for Money {
+		type Output = Money;
+
+		fn sub(self, other: Money) -> Money {
+			self.checked_sub(other).expect("Money overflow")
+		}
+	}
+
+	impl Mul<i64> for Money {
+		type Output = Money;
+
+		fn mul(self, factor: i64) -> Money {
+			self.checked_mul(factor).expect("Money overflow")
+		}
+	}
+
+	impl Neg for Money {
+		type Output = Money;
+
+		fn neg(self) -> Money {
+			Money(self.0.checked_neg().expect("Money overflow"))
+		}
+	}
+
+	impl AddAssign for Money {
+		fn add_assign(&mut self, other: Money) {
+			*self = *self + other;
+		}
+	}
+
+	impl SubAssign for Money {
+		fn sub_assign(&mut self, other: Money) {
+			*self = *self - other;
+		}
+	}
+
+	impl Sum for Money {
+		fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+			iter.fold(Money::ZERO, |total, amount| total + amount)
+		}
+	}
+
+	impl<'a> Sum<&'a Money> for Money {
+		fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Money {
+			iter.copied().sum()
+		}
+	}
+
+	/// Formats the amount with two decimal places, e.g. "12.50" or "-0.25".
+	/// A sign is only shown if requested with `{:+}` or the amount is negative.
+	impl fmt::Display for Money {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			let sign = match (self.0 < 0, f.sign_plus()) {
+				(true, _) => "-",
+				(false, true) => "+",
+				(false, false) => ""
+			};
+			let cents = self.0.unsigned_abs();
+			write!(f, "{}{}.{:02}", sign, cents / 100, cents % 100)
+		}
+	}
+
+	/// Parses amounts written in currency units with at most two decimal
+	/// places, such as "10", "-3" or "12.5"
+	impl FromStr for Money {
+		type Err = BlackjackError;
+
+		fn from_str(s: &str) -> Result<Money, BlackjackError> {
+			let s = s.trim();
+			let (negative, digits) = match s.strip_prefix('-') {
+				Some(rest) => (true, rest),
+				None => (false, s.strip_prefix('+').unwrap_or(s))
+			};
+			let (units, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+			let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+			if units.is_empty() || fraction.len() > 2 || !is_digits(units) || !is_digits(fraction) {
+				return Err(BlackjackError::InvalidAmount);
+			}
+			let fraction_cents = match fraction.len() {
+				0 => 0,
+				1 => fraction.parse::<i64>().map_err(|_| BlackjackError::InvalidAmount)? * 10,
+				_ => fraction.parse::<i64>().map_err(|_| BlackjackError::InvalidAmount)?
+			};
+			let cents = units.parse::<i64>().ok()
+				.and_then(|units| units.checked_mul(100))
+				.and_then(|cents| cents.checked_add(fraction_cents))
+				.ok_or(BlackjackError::InvalidAmount)?;
+			Ok(Money(match negative {
+				true => -cents,
+				false => cents
+			}))
+		}
+	}
+}
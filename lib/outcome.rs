@@ -0,0 +1,91 @@
+// Copyright (C) 2019 Arc676/Alessandro Vinciguerra <alesvinciguerra@gmail.com>
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation (version 3).
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+pub mod outcome {
+	use crate::card::card::Card;
+	use crate::money::money::Money;
+
+	/// How the dealer's hand finished
+	#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+	pub enum DealerOutcome {
+		/// The dealer went over 21
+		Bust,
+		/// The dealer stood on the given total
+		Total(u32),
+		/// The dealer has a natural blackjack
+		Blackjack
+	}
+
+	/// Result of a single hand once bets are settled
+	#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+	pub enum HandOutcome {
+		/// The hand beat the dealer and is paid even money
+		Win,
+		/// The hand lost its wager
+		Lose,
+		/// The hand tied the dealer and the wager is returned
+		Push,
+		/// The hand is a natural blackjack and is paid at the table's blackjack payout
+		Blackjack,
+		/// The hand was surrendered for half its wager
+		Surrender
+	}
+
+	/// How a single hand was settled
+	#[derive(Clone, Debug, PartialEq, Eq)]
+	pub struct HandReport {
+		/// The hand's cards in the order they were drawn
+		pub cards: Vec<Card>,
+		/// Final value of the hand
+		pub value: u32,
+		/// Total wager on the hand, including any double
+		pub wager: Money,
+		pub outcome: HandOutcome,
+		/// Amount won on the hand, negative if the wager (or part of it) was lost
+		pub payout: Money
+	}
+
+	/// How a player's bets were settled at the end of a round
+	#[derive(Clone, Debug, PartialEq, Eq)]
+	pub struct SettlementReport {
+		pub dealer: DealerOutcome,
+		/// Reports for each of the player's hands in order
+		pub hands: Vec<HandReport>,
+		/// The player's insurance bet, 0 if they didn't insure
+		pub insurance: Money,
+		/// Amount won on the insurance bet, negative if it was lost
+		pub insurance_payout: Money,
+		/// The player's balance after settling
+		pub balance: Money
+	}
+
+	impl HandOutcome {
+		pub fn name(&self) -> &'static str {
+			match self {
+				HandOutcome::Win => "Win",
+				HandOutcome::Lose => "Lose",
+				HandOutcome::Push => "Push",
+				HandOutcome::Blackjack => "Blackjack",
+				HandOutcome::Surrender => "Surrender"
+			}
+		}
+	}
+
+	impl SettlementReport {
+		/// Total amount won or lost this round across all hands and insurance
+		pub fn net(&self) -> Money {
+			self.hands.iter().map(|hand| hand.payout).sum::<Money>() + self.insurance_payout
+		}
+	}
+}
@@ -13,46 +13,112 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 pub mod player {
-	use std::vec;
+	use std::cmp::Ordering;
+	use std::fmt;
 	use std::slice::Iter;
 	use crate::card::card::*;
+	use crate::error::error::BlackjackError;
+	use crate::money::money::Money;
+	use crate::outcome::outcome::*;
+	use crate::rules::rules::*;
+
+	/// A decision a player can make during a round
+	#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+	pub enum Action {
+		Hit,
+		Stand,
+		Double,
+		Split,
+		Surrender,
+		Insurance
+	}
 
-	#[no_mangle]
 	pub struct Player {
 		name: String,
-		is_dealer: bool,
 		hands: Vec<Hand>,
-		balance: i32,
-		standing: i32
+		balance: Money,
+		standing: Money,
+		rules: Rules
 	}
 
-	#[no_mangle]
 	#[derive(Clone)]
 	pub struct Hand {
 		cards: Vec<Card>,
 		can_surrender: bool,
 		surrendered: bool,
 		is_set: bool,
-		wager: i32
+		is_split: bool,
+		split_aces: bool,
+		spot: usize,
+		wager: Money,
+		insurance: Money,
+		even_money: bool
+	}
+
+	/// Breakdown of what a hand is worth
+	#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+	pub struct HandValue {
+		/// Total with every Ace counted as 1
+		pub hard: u32,
+		/// Total with one Ace counted as 11, or the hard total if that would
+		/// go over 21 or the hand has no Ace
+		pub soft: u32,
+		/// Whether an Ace is counted as 11 in the hand's best total
+		pub is_soft: bool,
+		/// Whether the hand is a natural blackjack
+		pub is_blackjack: bool,
+		/// Whether the hand is over 21
+		pub is_bust: bool,
+		/// Whether the hand is two cards of the same rank
+		pub is_pair: bool
+	}
+
+	impl Action {
+		pub fn name(&self) -> &'static str {
+			match self {
+				Action::Hit => "hit",
+				Action::Stand => "stand",
+				Action::Double => "double",
+				Action::Split => "split",
+				Action::Surrender => "surrender",
+				Action::Insurance => "insurance"
+			}
+		}
+
+		pub fn from_name(name: &str) -> Option<Action> {
+			match name {
+				"hit" => Some(Action::Hit),
+				"stand" => Some(Action::Stand),
+				"double" => Some(Action::Double),
+				"split" => Some(Action::Split),
+				"surrender" => Some(Action::Surrender),
+				"insurance" => Some(Action::Insurance),
+				_ => None
+			}
+		}
 	}
 
 	impl Player {
-		pub fn new(name: String, is_dealer: bool, balance: i32) -> Player {
+		pub fn new(name: String, balance: Money) -> Player {
+			Player::with_rules(name, balance, Rules::default())
+		}
+
+		pub fn with_rules(name: String, balance: Money, rules: Rules) -> Player {
 			Player {
-				name, is_dealer, hands: Vec::with_capacity(2), balance, standing: 0
+				name, hands: Vec::with_capacity(2), balance, standing: Money::ZERO, rules
 			}
 		}
 
-		fn win(&mut self, amt: i32) {
+		fn win(&mut self, amt: Money) {
 			self.balance += amt;
 			self.standing += amt;
 		}
 
-		fn lose(&mut self, amt: i32) {
+		fn lose(&mut self, amt: Money) {
 			self.win(-amt);
 		}
 
-		pub fn hand_iter(&self) -> Iter<Hand> {
+		pub fn hand_iter(&self) -> Iter<'_, Hand> {
 			self.hands.iter()
 		}
 
@@ -65,118 +131,341 @@ pub mod player {
 		}
 
 		pub fn get_name(&self) -> &str {
-			&*self.name
+			&self.name
 		}
 
-		pub fn get_balance(&self) -> i32 {
+		pub fn get_balance(&self) -> Money {
 			self.balance
 		}
 
-		pub fn get_standing(&self) -> i32 {
+		pub fn get_standing(&self) -> Money {
 			self.standing
 		}
 
-		fn get_playing_hand(&self) -> &Hand {
-			for hand in &self.hands {
-				if !hand.get_is_set() {
-					return hand;
-				}
+		/// Total amount currently at risk across all live hands, including
+		/// insurance. Surrendered hands are excluded as their loss has already
+		/// been deducted from the balance.
+		pub fn get_total_wager(&self) -> Money {
+			self.hands.iter().filter(|hand| !hand.surrendered).map(|hand| hand.wager).sum::<Money>() + self.get_insurance()
+		}
+
+		/// Number of spots the player bet on this round
+		pub fn get_spot_count(&self) -> usize {
+			self.hands.iter().map(|hand| hand.spot + 1).max().unwrap_or(0)
+		}
+
+		/// Number of hands played from the given spot, including split hands
+		fn get_spot_hand_count(&self, spot: usize) -> usize {
+			self.hands.iter().filter(|hand| hand.spot == spot).count()
+		}
+
+		/// The original hand dealt to the given spot
+		fn get_spot_hand_mut(&mut self, spot: usize) -> Result<&mut Hand, BlackjackError> {
+			self.hands.iter_mut().find(|hand| hand.spot == spot).ok_or(BlackjackError::NoActiveHand)
+		}
+
+		/// Portion of the balance not already reserved for wagers this round
+		pub fn get_available_balance(&self) -> Money {
+			self.balance - self.get_total_wager()
+		}
+
+		fn can_afford(&self, amount: Money) -> bool {
+			self.get_available_balance() >= amount && self.winnings_within_limit(amount)
+		}
+
+		fn reserve(&self, amount: Money) -> Result<(), BlackjackError> {
+			if !self.winnings_within_limit(amount) {
+				return Err(BlackjackError::InvalidWager(amount));
+			}
+			match self.can_afford(amount) {
+				true => Ok(()),
+				false => Err(BlackjackError::InsufficientFunds)
 			}
-			panic!("Player has no hands");
 		}
 
-		fn get_playing_hand_mut(&mut self) -> &mut Hand {
-			for hand in &mut self.hands {
-				if !hand.get_is_set() {
-					return hand;
-				}
+		/// Whether the balance stays within `Money::MAX` however the round
+		/// goes with a further `amount` at stake. No bet wins more than twice
+		/// what was staked.
+		pub(crate) fn winnings_within_limit(&self, amount: Money) -> bool {
+			self.get_total_wager().checked_add(amount)
+				.and_then(|at_stake| at_stake.checked_mul(2))
+				.and_then(|winnings| self.balance.checked_add(winnings))
+				.is_some_and(|balance| balance <= Money::MAX)
+		}
+
+		/// Actions available to the player on the hand currently being played.
+		/// Insurance is never listed here since it depends on the dealer's
+		/// upcard; the table offers it when the dealer shows an Ace.
+		pub fn legal_actions(&self) -> Vec<Action> {
+			let idx = match self.get_playing_hand_index() {
+				Some(idx) => idx,
+				None => return Vec::new()
+			};
+			let hand = &self.hands[idx];
+			let mut actions = hand.legal_actions(&self.rules);
+			actions.retain(|action| match action {
+				Action::Double => self.can_afford(hand.wager) ||
+					(self.rules.double_for_less && self.get_available_balance().is_positive()),
+				Action::Split => self.get_spot_hand_count(hand.spot) < self.rules.max_hands && self.can_afford(hand.wager),
+				_ => true
+			});
+			actions
+		}
+
+		/// Perform an action on the hand currently being played, refusing it if
+		/// it is not legal
+		pub fn apply(&mut self, action: Action, deck: &mut Deck) -> Result<(), BlackjackError> {
+			self.get_playing_hand()?;
+			if !self.legal_actions().contains(&action) {
+				return Err(BlackjackError::IllegalAction(action));
 			}
-			panic!("Player has no hands");
+			match action {
+				Action::Hit => self.hit(deck).map(|_| ()),
+				Action::Stand => self.stand(),
+				Action::Double => self.double(deck).map(|_| ()),
+				Action::Split => self.split(deck),
+				Action::Surrender => self.surrender(),
+				Action::Insurance => Err(BlackjackError::IllegalAction(action))
+			}
+		}
+
+		/// Whether any spot can still take insurance for half its wager
+		pub(crate) fn can_take_insurance(&self) -> bool {
+			self.get_insurable_hand_index().is_some()
+		}
+
+		/// Insure the first spot that isn't insured yet for half its wager
+		pub(crate) fn take_insurance(&mut self) -> Result<(), BlackjackError> {
+			let idx = self.get_insurable_hand_index().ok_or(BlackjackError::IllegalAction(Action::Insurance))?;
+			let (spot, amount) = (self.hands[idx].spot, self.hands[idx].wager.half());
+			self.insure_spot(spot, amount)
+		}
+
+		/// The first hand that can still take insurance for half its wager
+		fn get_insurable_hand_index(&self) -> Option<usize> {
+			self.hands.iter().position(|hand| {
+				hand.can_insure() && hand.wager.half().is_positive() && self.can_afford(hand.wager.half())
+			})
+		}
+
+		pub fn get_rules(&self) -> &Rules {
+			&self.rules
+		}
+
+		pub fn set_rules(&mut self, rules: Rules) {
+			self.rules = rules;
+		}
+
+		/// Index of the hand currently being played, if any
+		pub fn get_playing_hand_index(&self) -> Option<usize> {
+			self.hands.iter().position(|hand| !hand.get_is_set())
+		}
+
+		fn get_playing_hand(&self) -> Result<&Hand, BlackjackError> {
+			self.hands.iter().find(|hand| !hand.get_is_set()).ok_or(BlackjackError::NoActiveHand)
 		}
 
+		fn get_playing_hand_mut(&mut self) -> Result<&mut Hand, BlackjackError> {
+			self.hands.iter_mut().find(|hand| !hand.get_is_set()).ok_or(BlackjackError::NoActiveHand)
+		}
+
+		/// Value of the player's first hand, or 0 if they hold no cards
 		pub fn first_hand_value(&self) -> u32 {
-			if self.has_busted() {
-				return 0
-			}
-			self.hands[0].value(false)
+			self.hands.first().map_or(0, |hand| hand.value())
 		}
 
 		pub fn can_surrender_hand(&self) -> bool {
-			self.get_playing_hand().can_surrender_hand()
+			self.get_playing_hand().is_ok_and(|hand| hand.can_surrender(&self.rules))
 		}
 
 		pub fn can_split_hand(&self) -> bool {
-			self.get_playing_hand().can_split_hand()
+			self.get_playing_hand().is_ok_and(|hand| {
+				self.get_spot_hand_count(hand.spot) < self.rules.max_hands && hand.can_split(&self.rules)
+			})
+		}
+
+		pub fn can_double_hand(&self) -> bool {
+			self.get_playing_hand().is_ok_and(|hand| hand.can_double(&self.rules))
 		}
 
-		pub fn surrender(&mut self) -> bool {
-			let loss = self.get_playing_hand().get_wager() / 2;
-			if self.get_playing_hand_mut().surrender() {
-				self.lose(loss);
-				return true;
+		pub fn surrender(&mut self) -> Result<(), BlackjackError> {
+			if !self.can_surrender_hand() {
+				return Err(BlackjackError::IllegalAction(Action::Surrender));
 			}
-			false
+			let hand = self.get_playing_hand_mut()?;
+			let loss = hand.get_surrender_loss();
+			hand.surrender()?;
+			self.lose(loss);
+			Ok(())
 		}
 
-		pub fn has_lost(&self) -> bool {
-			for hand in &self.hands {
-				if !hand.lost() {
-					return false;
-				}
+		/// Whether the hand on the given spot may be surrendered before it is played
+		pub fn can_surrender_spot(&self, spot: usize) -> bool {
+			self.hands.iter().find(|hand| hand.spot == spot)
+				.is_some_and(|hand| !hand.is_set && hand.can_surrender(&self.rules))
+		}
+
+		/// Surrender the hand on the given spot, such as during early surrender
+		/// when each spot is offered the choice before play begins
+		pub fn surrender_spot(&mut self, spot: usize) -> Result<(), BlackjackError> {
+			if !self.can_surrender_spot(spot) {
+				return Err(BlackjackError::IllegalAction(Action::Surrender));
 			}
-			true
+			let hand = self.get_spot_hand_mut(spot)?;
+			let loss = hand.get_surrender_loss();
+			hand.surrender()?;
+			self.lose(loss);
+			Ok(())
+		}
+
+		/// Total insurance placed across all spots
+		pub fn get_insurance(&self) -> Money {
+			self.hands.iter().map(|hand| hand.insurance).sum()
+		}
+
+		pub fn took_even_money(&self) -> bool {
+			self.hands.iter().any(|hand| hand.even_money)
 		}
 
-		pub fn stand(&mut self) {
-			self.get_playing_hand_mut().set();
+		/// Whether the player may still place an insurance bet on any spot
+		pub fn can_insure(&self) -> bool {
+			self.hands.iter().any(|hand| hand.can_insure())
 		}
 
-		pub fn bet(&mut self, wager: i32, deck: &mut Deck) {
-			self.hands.push(Hand::new(wager, deck));
+		/// Insure the first spot. See `insure_spot`.
+		pub fn insure(&mut self, amount: Money) -> Result<(), BlackjackError> {
+			self.insure_spot(0, amount)
 		}
 
-		pub fn split(&mut self, deck: &mut Deck) -> bool {
-			match self.get_playing_hand_mut().split(deck) {
-				Some(newhand) => {
-					self.hands.push(newhand);
-					true
-				},
-				None => false
+		/// Place an insurance bet of up to half the spot's wager, rounded down
+		/// to the cent. The bet pays 2:1 if the dealer has blackjack.
+		pub fn insure_spot(&mut self, spot: usize, amount: Money) -> Result<(), BlackjackError> {
+			let wager = self.get_spot_hand_mut(spot)?.wager;
+			if !self.get_spot_hand_mut(spot)?.can_insure() {
+				return Err(BlackjackError::IllegalAction(Action::Insurance));
 			}
+			if !amount.is_positive() || amount > wager.half() {
+				return Err(BlackjackError::InvalidWager(amount));
+			}
+			self.reserve(amount)?;
+			self.get_spot_hand_mut(spot)?.insurance = amount;
+			Ok(())
+		}
+
+		/// Take even money on the first spot. See `take_even_money_spot`.
+		pub fn take_even_money(&mut self) -> Result<(), BlackjackError> {
+			self.take_even_money_spot(0)
 		}
 
-		pub fn double(&mut self, deck: &mut Deck) -> bool {
-			self.get_playing_hand_mut().double_wager(deck)
+		/// Accept an even money payout on a natural blackjack against a dealer Ace
+		pub fn take_even_money_spot(&mut self, spot: usize) -> Result<(), BlackjackError> {
+			let hand = self.get_spot_hand_mut(spot)?;
+			if !hand.can_insure() || !hand.is_blackjack() {
+				return Err(BlackjackError::IllegalAction(Action::Insurance));
+			}
+			hand.even_money = true;
+			hand.set();
+			Ok(())
 		}
 
-		pub fn hit(&mut self, deck: &mut Deck) -> bool {
-			let hand = self.get_playing_hand_mut();
-			hand.hit(deck)
+		/// Drop the player's hands without settling them
+		pub(crate) fn clear_hands(&mut self) {
+			self.hands.clear();
 		}
 
-		pub fn has_busted(&self) -> bool {
+		pub fn has_lost(&self) -> bool {
 			for hand in &self.hands {
-				if hand.busted() {
-					return true;
+				if !hand.lost() {
+					return false;
 				}
 			}
-			false
+			true
 		}
 
-		pub fn hand_is_soft(&self) -> bool {
-			self.get_playing_hand().is_soft()
+		pub fn stand(&mut self) -> Result<(), BlackjackError> {
+			self.get_playing_hand_mut()?.set();
+			Ok(())
 		}
 
-		pub fn play_as_dealer(&mut self, mut deck: &mut Deck) -> u32 {
-			let hand = &mut self.hands[0];
-			while hand.value(false) < 17 {
-				hand.hit(&mut deck);
+		/// Place a wager on a new spot and deal its starting hand. Calling this
+		/// more than once per round plays several spots at once, up to the
+		/// number of spots the rules allow. The wager must be within the table
+		/// limits set by the rules.
+		pub fn bet(&mut self, wager: Money, deck: &mut Deck) -> Result<(), BlackjackError> {
+			if self.get_spot_count() >= self.rules.max_spots {
+				return Err(BlackjackError::TooManySpots);
 			}
-			hand.set();
-			match self.has_busted() {
-				true => 0,
-				false => self.first_hand_value()
+			let already_bet = self.hands.iter().map(|hand| hand.wager).sum();
+			self.rules.check_bet(wager, already_bet)?;
+			self.reserve(wager)?;
+			let mut hand = Hand::new(wager, deck)?;
+			hand.spot = self.get_spot_count();
+			self.hands.push(hand);
+			Ok(())
+		}
+
+		/// Split the current hand. The new hand is played right after it,
+		/// before the player's next spot.
+		pub fn split(&mut self, deck: &mut Deck) -> Result<(), BlackjackError> {
+			let idx = self.get_playing_hand_index().ok_or(BlackjackError::NoActiveHand)?;
+			if self.get_spot_hand_count(self.hands[idx].spot) >= self.rules.max_hands ||
+				!self.hands[idx].can_split(&self.rules) {
+				return Err(BlackjackError::IllegalAction(Action::Split));
+			}
+			self.reserve(self.hands[idx].wager)?;
+			let rules = self.rules.clone();
+			let newhand = self.hands[idx].split(deck, &rules)?;
+			let spot = newhand.spot;
+			self.hands.insert(idx + 1, newhand);
+			if self.get_spot_hand_count(spot) >= rules.max_hands {
+				// Split aces can't be resplit past the hand limit, so they stand
+				for hand in self.hands.iter_mut().filter(|hand| hand.spot == spot) {
+					hand.stand_split_ace(&rules, false);
+				}
 			}
+			Ok(())
+		}
+
+		/// Double the wager on the current hand and draw its final card. If the
+		/// rules allow doubling for less, a player who can't cover the full
+		/// amount doubles for their remaining balance instead.
+		pub fn double(&mut self, deck: &mut Deck) -> Result<Card, BlackjackError> {
+			if !self.get_playing_hand()?.can_double(&self.rules) {
+				return Err(BlackjackError::IllegalAction(Action::Double));
+			}
+			let available = self.get_available_balance();
+			let wager = self.get_playing_hand()?.wager;
+			let amount = if available >= wager {
+				wager
+			} else if self.rules.double_for_less && available.is_positive() {
+				available
+			} else {
+				return Err(BlackjackError::InsufficientFunds);
+			};
+			self.reserve(amount)?;
+			let rules = self.rules.clone();
+			self.get_playing_hand_mut()?.double_wager(deck, &rules, amount)
+		}
+
+		/// Draw a card to the current hand and return it
+		pub fn hit(&mut self, deck: &mut Deck) -> Result<Card, BlackjackError> {
+			if !self.get_playing_hand()?.can_hit(&self.rules) {
+				return Err(BlackjackError::IllegalAction(Action::Hit));
+			}
+			self.get_playing_hand_mut()?.hit(deck)
+		}
+
+		/// Whether every one of the player's hands has gone over 21
+		pub fn has_busted(&self) -> bool {
+			!self.hands.is_empty() && self.hands.iter().all(|hand| hand.busted())
+		}
+
+		pub fn has_blackjack(&self) -> bool {
+			self.hands.len() == 1 && self.hands[0].is_blackjack()
+		}
+
+		pub fn hand_is_soft(&self) -> bool {
+			self.get_playing_hand().is_ok_and(|hand| hand.is_soft())
 		}
 
 		pub fn is_playing(&self) -> bool {
@@ -188,39 +477,109 @@ pub mod player {
 			false
 		}
 
-		pub fn game_over(&mut self, dealer_value: u32) {
-			if self.is_dealer {
-				self.hands.clear();
-				return;
-			}
-			let mut total_delta: i32 = 0;
-			for hand in &mut self.hands {
-				if hand.did_surrender() {
-					continue;
-				}
-				let value = hand.value(false);
+		/// Settle the player's bets against the dealer's result and clear
+		/// their hands. Returns a report of how each hand was settled.
+		pub fn game_over(&mut self, dealer: DealerOutcome) -> SettlementReport {
+			let mut report = SettlementReport {
+				dealer, hands: Vec::with_capacity(self.hands.len()), insurance: self.get_insurance(),
+				insurance_payout: Money::ZERO, balance: self.balance
+			};
+			let mut total_delta = Money::ZERO;
+			for hand in &self.hands {
 				let wager = hand.get_wager();
-				if value > dealer_value && !hand.lost() {
-					total_delta += wager;
-				} else if value < dealer_value || hand.lost() {
-					total_delta -= wager;
+				if hand.insurance.is_positive() {
+					report.insurance_payout += match dealer {
+						DealerOutcome::Blackjack => hand.insurance * 2,
+						_ => -hand.insurance
+					};
 				}
+				let outcome = match hand.even_money {
+					true => HandOutcome::Win,
+					false => hand.outcome(dealer, &self.rules)
+				};
+				let payout = match outcome {
+					HandOutcome::Win => wager,
+					HandOutcome::Lose => -wager,
+					HandOutcome::Push => Money::ZERO,
+					HandOutcome::Blackjack => self.rules.blackjack_payout.pay(wager),
+					HandOutcome::Surrender => -hand.get_surrender_loss()
+				};
+				// The forfeited part of a surrendered wager was deducted when surrendering
+				total_delta += match hand.did_surrender() {
+					true => payout + hand.get_surrender_loss(),
+					false => payout
+				};
+				report.hands.push(HandReport {
+					cards: hand.cards.clone(), value: hand.value(), wager, outcome, payout
+				});
 			}
+			total_delta += report.insurance_payout;
 			self.win(total_delta);
 			self.hands.clear();
+			report.balance = self.balance;
+			report
+		}
+	}
+
+	impl HandValue {
+		/// The best total the hand can make
+		pub fn total(&self) -> u32 {
+			self.soft
+		}
+
+		/// Every total the hand can be counted as without going over 21,
+		/// lowest first. A bust hand only has its hard total.
+		pub fn totals(&self) -> Vec<u32> {
+			match self.is_soft {
+				true => vec![self.hard, self.soft],
+				false => vec![self.hard]
+			}
+		}
+	}
+
+	/// Formats the hand's totals separated by a slash, e.g. "7/17" for a
+	/// soft hand and "17" for a hard one
+	impl fmt::Display for HandValue {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			match self.is_soft {
+				true => write!(f, "{}/{}", self.hard, self.soft),
+				false => write!(f, "{}", self.hard)
+			}
 		}
 	}
 
 	impl Hand {
-		pub fn new(wager: i32, deck: &mut Deck) -> Hand {
-			let mut hand = Hand { cards: Vec::with_capacity(11), can_surrender: true, surrendered: false, is_set: false, wager };
-			for _ in 0..2 {
-				hand.cards.push(deck.next_card())
+		pub fn new(wager: Money, deck: &mut Deck) -> Result<Hand, BlackjackError> {
+			Hand::deal(wager, deck, 2)
+		}
+
+		pub(crate) fn deal(wager: Money, deck: &mut Deck, card_count: usize) -> Result<Hand, BlackjackError> {
+			let mut hand = Hand {
+				cards: Vec::with_capacity(11), can_surrender: true, surrendered: false, is_set: false,
+				is_split: false, split_aces: false, spot: 0, wager, insurance: Money::ZERO, even_money: false
+			};
+			for _ in 0..card_count {
+				hand.cards.push(deck.next_card()?)
 			}
+			// A natural has nothing left to play
+			if hand.is_blackjack() {
+				hand.set();
+			}
+			Ok(hand)
+		}
+
+		pub(crate) fn add_card(&mut self, card: Card) {
+			self.cards.push(card);
+		}
+
+		/// A copy of the hand holding only its first `count` cards
+		pub(crate) fn truncated(&self, count: usize) -> Hand {
+			let mut hand = self.clone();
+			hand.cards.truncate(count);
 			hand
 		}
 
-		pub fn card_iter(&self) -> Iter<Card> {
+		pub fn card_iter(&self) -> Iter<'_, Card> {
 			self.cards.iter()
 		}
 
@@ -229,7 +588,7 @@ pub mod player {
 		}
 
 		pub fn get_card_at(&self, idx: usize) -> Card {
-			self.cards[idx].clone()
+			self.cards[idx]
 		}
 
 		pub fn set(&mut self) {
@@ -241,13 +600,19 @@ pub mod player {
 			self.is_set
 		}
 
-		pub fn surrender(&mut self) -> bool {
-			if self.can_surrender {
-				self.surrendered = true;
-				self.set();
-				return true;
+		pub fn surrender(&mut self) -> Result<(), BlackjackError> {
+			if !self.can_surrender {
+				return Err(BlackjackError::IllegalAction(Action::Surrender));
 			}
-			false
+			self.surrendered = true;
+			self.set();
+			Ok(())
+		}
+
+		/// Part of the wager lost by surrendering: half, with an odd cent
+		/// going to the house
+		pub fn get_surrender_loss(&self) -> Money {
+			self.wager - self.wager.half()
 		}
 
 		pub fn did_surrender(&self) -> bool {
@@ -258,71 +623,205 @@ pub mod player {
 			self.can_surrender
 		}
 
+		/// Whether the rules allow surrendering this hand
+		pub fn can_surrender(&self, rules: &Rules) -> bool {
+			self.can_surrender && rules.surrender != SurrenderRule::None &&
+				(!self.is_split || rules.surrender_after_split)
+		}
+
 		pub fn lost(&self) -> bool {
 			self.surrendered || self.busted()
 		}
 
-		pub fn split(&mut self, deck: &mut Deck) -> Option<Hand> {
-			if self.can_split_hand() {
-				let card = self.cards[1];
-				self.cards[1] = deck.next_card();
-				return Some(Hand {
-					cards: vec![card, deck.next_card()], can_surrender: true, surrendered: false, is_set: false, wager: self.wager
-				});
+		/// Split the pair into two hands, each drawing a second card. Unless the
+		/// rules allow hitting split aces, each ace receives only that one card
+		/// and the hand stands, except where it may be resplit.
+		pub fn split(&mut self, deck: &mut Deck, rules: &Rules) -> Result<Hand, BlackjackError> {
+			if !self.can_split(rules) {
+				return Err(BlackjackError::IllegalAction(Action::Split));
+			}
+			let first = deck.next_card()?;
+			let second = deck.next_card()?;
+			let card = self.cards[1];
+			self.cards[1] = first;
+			self.is_split = true;
+			self.split_aces = card.rank == Rank::Ace;
+			let mut hand = Hand {
+				cards: vec![card, second], can_surrender: true, surrendered: false, is_set: false,
+				is_split: true, split_aces: self.split_aces, spot: self.spot, wager: self.wager,
+				insurance: Money::ZERO, even_money: false
+			};
+			self.stand_split_ace(rules, true);
+			hand.stand_split_ace(rules, true);
+			Ok(hand)
+		}
+
+		/// Stand a split ace that may not be hit, unless it may still be resplit
+		fn stand_split_ace(&mut self, rules: &Rules, can_resplit: bool) {
+			if self.split_aces && !rules.hit_split_aces && !(can_resplit && self.can_split(rules)) {
+				self.set();
 			}
-			None
 		}
 
-		pub fn can_split_hand(&self) -> bool {
-			self.cards.len() == 2 && self.cards[0].score() == self.cards[1].score()
+		/// Whether the rules allow splitting this hand
+		pub fn can_split(&self, rules: &Rules) -> bool {
+			!self.is_set && self.cards.len() == 2 && rules.split.allows(&self.cards[0], &self.cards[1]) &&
+				(!self.split_aces || rules.resplit_aces)
+		}
+
+		/// Whether the hand resulted from splitting aces
+		pub fn is_split_aces(&self) -> bool {
+			self.split_aces
+		}
+
+		/// Whether the rules allow drawing to this hand
+		pub fn can_hit(&self, rules: &Rules) -> bool {
+			!self.is_set && (!self.split_aces || rules.hit_split_aces)
+		}
+
+		pub fn is_split(&self) -> bool {
+			self.is_split
 		}
 
-		pub fn double_wager(&mut self, deck: &mut Deck) -> bool {
-			self.wager *= 2;
+		/// Index of the spot the hand was dealt to or split from
+		pub fn get_spot(&self) -> usize {
+			self.spot
+		}
+
+		pub fn get_insurance(&self) -> Money {
+			self.insurance
+		}
+
+		/// Whether the hand may still be insured. Insurance is only taken on
+		/// the original two-card hand, which stands at once if it is a natural,
+		/// and may not be combined with even money.
+		pub fn can_insure(&self) -> bool {
+			self.cards.len() == 2 && !self.is_split && (!self.is_set || self.is_blackjack()) &&
+				self.insurance == Money::ZERO && !self.even_money
+		}
+
+		/// Actions the rules allow on this hand, without regard to the player's
+		/// bankroll or how many hands they already hold
+		pub fn legal_actions(&self, rules: &Rules) -> Vec<Action> {
+			if self.is_set {
+				return Vec::new();
+			}
+			let mut actions = Vec::new();
+			if self.can_hit(rules) {
+				actions.push(Action::Hit);
+			}
+			actions.push(Action::Stand);
+			if self.can_double(rules) {
+				actions.push(Action::Double);
+			}
+			if self.can_split(rules) {
+				actions.push(Action::Split);
+			}
+			if self.can_surrender(rules) {
+				actions.push(Action::Surrender);
+			}
+			actions
+		}
+
+		pub fn can_double(&self, rules: &Rules) -> bool {
+			self.can_hit(rules) && self.cards.len() == 2 &&
+				(!self.is_split || rules.double_after_split) &&
+				rules.double.allows(self.value())
+		}
+
+		/// Raise the wager by the given amount, at most the original wager, and
+		/// draw a single final card
+		pub fn double_wager(&mut self, deck: &mut Deck, rules: &Rules, amount: Money) -> Result<Card, BlackjackError> {
+			if !self.can_double(rules) {
+				return Err(BlackjackError::IllegalAction(Action::Double));
+			}
+			if !amount.is_positive() || amount > self.wager {
+				return Err(BlackjackError::InvalidWager(amount));
+			}
+			let card = self.hit(deck)?;
+			self.wager += amount;
 			self.set();
-			self.hit(deck)
+			Ok(card)
 		}
 
-		pub fn hit(&mut self, deck: &mut Deck) -> bool {
-			self.cards.push(deck.next_card());
-			let ret = self.busted();
-			if ret {
+		pub fn hit(&mut self, deck: &mut Deck) -> Result<Card, BlackjackError> {
+			if self.is_set {
+				return Err(BlackjackError::IllegalAction(Action::Hit));
+			}
+			let card = deck.next_card()?;
+			self.cards.push(card);
+			if self.busted() {
 				self.set();
 			}
 			self.can_surrender = false;
-			ret
+			Ok(card)
 		}
 
-		pub fn busted(&self) -> bool {
-			self.value(false) > 21
+		/// Whether the hand is a natural blackjack, i.e. 21 with its first two cards.
+		/// Two cards totalling 21 after a split do not count.
+		pub fn is_blackjack(&self) -> bool {
+			self.get_value().is_blackjack
 		}
 
-		pub fn get_wager(&self) -> i32 {
-			self.wager
+		pub fn busted(&self) -> bool {
+			self.value() > 21
 		}
 
-		pub fn value(&self, get_aces: bool) -> u32 {
-			let mut total: u32 = 0;
-			let mut aces: u32 = 0;
-			for card in &self.cards {
-				let value = card.score();
-				if value == 11 {
-					aces += 1
-				}
-				total += value;
+		/// Result of the hand against the dealer's. A late surrender is void
+		/// against a dealer blackjack and the hand loses.
+		pub fn outcome(&self, dealer: DealerOutcome, rules: &Rules) -> HandOutcome {
+			if self.surrendered {
+				return match dealer == DealerOutcome::Blackjack && rules.surrender == SurrenderRule::Late {
+					true => HandOutcome::Lose,
+					false => HandOutcome::Surrender
+				};
 			}
-			while total > 21 && aces > 0 {
-				total -= 10;
-				aces -= 1;
+			if self.busted() {
+				return HandOutcome::Lose;
 			}
-			match get_aces {
-				true => aces,
-				false => total
+			match dealer {
+				DealerOutcome::Blackjack if self.is_blackjack() => HandOutcome::Push,
+				DealerOutcome::Blackjack => HandOutcome::Lose,
+				_ if self.is_blackjack() => HandOutcome::Blackjack,
+				DealerOutcome::Bust => HandOutcome::Win,
+				DealerOutcome::Total(total) => match self.value().cmp(&total) {
+					Ordering::Greater => HandOutcome::Win,
+					Ordering::Less => HandOutcome::Lose,
+					Ordering::Equal => HandOutcome::Push
+				}
+			}
+		}
+
+		pub fn get_wager(&self) -> Money {
+			self.wager
+		}
+
+		/// The best total the hand can make
+		pub fn value(&self) -> u32 {
+			self.get_value().total()
+		}
+
+		/// Hard and soft totals of the hand along with its notable properties
+		pub fn get_value(&self) -> HandValue {
+			let has_ace = self.cards.iter().any(|card| card.rank == Rank::Ace);
+			let hard = self.cards.iter().map(|card| match card.rank {
+				Rank::Ace => 1,
+				_ => card.score()
+			}).sum();
+			let soft = match has_ace && hard + 10 <= 21 {
+				true => hard + 10,
+				false => hard
+			};
+			HandValue {
+				hard, soft, is_soft: soft != hard,
+				is_blackjack: !self.is_split && self.cards.len() == 2 && soft == 21,
+				is_bust: hard > 21,
+				is_pair: self.cards.len() == 2 && self.cards[0].rank == self.cards[1].rank
 			}
 		}
 
 		pub fn is_soft(&self) -> bool {
-			self.value(true) > 0
+			self.get_value().is_soft
 		}
 	}
 }
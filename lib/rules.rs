@@ -0,0 +1,227 @@
+// Copyright (C) 2019 Arc676/Alessandro Vinciguerra <alesvinciguerra@gmail.com>
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation (version 3).
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+pub mod rules {
+	use crate::card::card::Card;
+	use crate::error::error::BlackjackError;
+	use crate::money::money::Money;
+
+	/// Hands on which the player may double down
+	#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+	pub enum DoubleRule {
+		/// Any two-card hand
+		AnyTwo,
+		/// Two-card hands worth 9, 10 or 11
+		NineToEleven,
+		/// Two-card hands worth 10 or 11
+		TenToEleven
+	}
+
+	/// Pairs that may be split
+	#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+	pub enum SplitRule {
+		/// Only cards of the same rank, e.g. K+K but not K+Q
+		SameRank,
+		/// Any two cards worth the same, e.g. K+Q
+		SameValue
+	}
+
+	/// When (if ever) a player may give up half their wager
+	#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+	pub enum SurrenderRule {
+		/// Surrender is not offered
+		None,
+		/// Surrender is offered after the dealer checks for blackjack. At
+		/// no-hole-card tables a surrendered hand still loses its whole wager
+		/// to a dealer blackjack.
+		Late,
+		/// Surrender is offered before the dealer checks for blackjack, so it
+		/// saves half the wager even against a dealer blackjack
+		Early
+	}
+
+	/// How the dealer's second card is handled
+	#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+	pub enum HoleCardRule {
+		/// The hole card is dealt face down and the dealer checks it for
+		/// blackjack when showing an Ace or a ten-valued card
+		Peek,
+		/// European no-hole-card: the dealer's second card is only dealt
+		/// after all players have acted
+		NoHoleCard
+	}
+
+	/// Payout ratio for a natural blackjack
+	#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+	pub enum BlackjackPayout {
+		ThreeToTwo,
+		SixToFive,
+		OneToOne
+	}
+
+	/// Decides when the dealer draws another card
+	pub trait DealerPolicy: Send {
+		/// Whether the dealer draws to a hand with the given total. `soft` is
+		/// set if an Ace in the hand is counted as 11.
+		fn hits(&self, value: u32, soft: bool) -> bool;
+	}
+
+	/// The dealer stands on all 17s (S17)
+	#[derive(Copy, Clone, Debug, Default)]
+	pub struct StandsOnSoft17;
+
+	/// The dealer draws to soft 17 and stands on hard 17 (H17)
+	#[derive(Copy, Clone, Debug, Default)]
+	pub struct HitsSoft17;
+
+	impl DealerPolicy for StandsOnSoft17 {
+		fn hits(&self, value: u32, _soft: bool) -> bool {
+			value < 17
+		}
+	}
+
+	impl DealerPolicy for HitsSoft17 {
+		fn hits(&self, value: u32, soft: bool) -> bool {
+			value < 17 || (value == 17 && soft)
+		}
+	}
+
+	/// House rules for a blackjack table
+	#[derive(Clone, Debug, PartialEq, Eq)]
+	pub struct Rules {
+		/// Whether the dealer draws to a soft 17
+		pub dealer_hits_soft_17: bool,
+		/// Treatment of the dealer's hole card
+		pub hole_card: HoleCardRule,
+		/// Hands that may be doubled
+		pub double: DoubleRule,
+		/// Whether hands resulting from a split may be doubled
+		pub double_after_split: bool,
+		/// Whether a player who can't cover the full double may double for
+		/// whatever they have left
+		pub double_for_less: bool,
+		/// Pairs that may be split
+		pub split: SplitRule,
+		/// Maximum number of hands a player may hold on one spot after splitting
+		pub max_hands: usize,
+		/// Maximum number of spots a player may bet on in one round
+		pub max_spots: usize,
+		/// Whether hands from split aces may draw more than one card
+		pub hit_split_aces: bool,
+		/// Whether a pair of aces formed after splitting aces may be split again
+		pub resplit_aces: bool,
+		/// Surrender variant offered at the table
+		pub surrender: SurrenderRule,
+		/// Whether hands resulting from a split may be surrendered
+		pub surrender_after_split: bool,
+		/// Payout for a natural blackjack
+		pub blackjack_payout: BlackjackPayout,
+		/// Smallest wager accepted on a spot
+		pub min_bet: Money,
+		/// Largest total a player may wager across all their spots in one
+		/// round, if limited
+		pub max_bet: Option<Money>,
+		/// Largest wager accepted on a single spot, if limited
+		pub max_spot_bet: Option<Money>
+	}
+
+	impl DoubleRule {
+		pub fn allows(&self, value: u32) -> bool {
+			match self {
+				DoubleRule::AnyTwo => true,
+				DoubleRule::NineToEleven => (9..=11).contains(&value),
+				DoubleRule::TenToEleven => (10..=11).contains(&value)
+			}
+		}
+	}
+
+	impl SplitRule {
+		pub fn allows(&self, first: &Card, second: &Card) -> bool {
+			match self {
+				SplitRule::SameRank => first.rank == second.rank,
+				SplitRule::SameValue => first.score() == second.score()
+			}
+		}
+	}
+
+	impl BlackjackPayout {
+		/// Winnings for a natural blackjack on the given wager, rounded down
+		/// to the cent
+		pub fn pay(&self, wager: Money) -> Money {
+			match self {
+				BlackjackPayout::ThreeToTwo => wager.ratio(3, 2),
+				BlackjackPayout::SixToFive => wager.ratio(6, 5),
+				BlackjackPayout::OneToOne => wager
+			}
+		}
+	}
+
+	impl Rules {
+		/// The built-in dealer policy selected by `dealer_hits_soft_17`
+		pub fn dealer_policy(&self) -> Box<dyn DealerPolicy> {
+			match self.dealer_hits_soft_17 {
+				true => Box::new(HitsSoft17),
+				false => Box::new(StandsOnSoft17)
+			}
+		}
+
+		/// Check a wager on one spot against the table limits, given the
+		/// amount the player already bet on their other spots this round
+		pub fn check_bet(&self, wager: Money, already_bet: Money) -> Result<(), BlackjackError> {
+			if !wager.is_positive() {
+				return Err(BlackjackError::InvalidWager(wager));
+			}
+			if wager < self.min_bet {
+				return Err(BlackjackError::BelowTableMinimum(self.min_bet));
+			}
+			if let Some(max) = self.max_spot_bet.filter(|&max| wager > max) {
+				return Err(BlackjackError::AboveTableMaximum(max));
+			}
+			if let Some(max) = self.max_bet {
+				match already_bet.checked_add(wager) {
+					Some(total) if total <= max => (),
+					_ => return Err(BlackjackError::AboveTableMaximum(max))
+				}
+			}
+			Ok(())
+		}
+
+		pub fn new() -> Rules {
+			Rules {
+				dealer_hits_soft_17: false,
+				hole_card: HoleCardRule::Peek,
+				double: DoubleRule::AnyTwo,
+				double_after_split: true,
+				double_for_less: false,
+				split: SplitRule::SameRank,
+				max_hands: 4,
+				max_spots: 3,
+				hit_split_aces: false,
+				resplit_aces: false,
+				surrender: SurrenderRule::Late,
+				surrender_after_split: false,
+				blackjack_payout: BlackjackPayout::ThreeToTwo,
+				min_bet: Money::from_cents(1),
+				max_bet: None,
+				max_spot_bet: None
+			}
+		}
+	}
+
+	impl Default for Rules {
+		fn default() -> Rules {
+			Rules::new()
+		}
+	}
+}
@@ -0,0 +1,414 @@
+// Copyright (C) 2019 Arc676/Alessandro Vinciguerra <alesvinciguerra@gmail.com>
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation (version 3).
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+pub mod table {
+	use crate::card::card::*;
+	use crate::counting::counting::CountingSystem;
+	use crate::dealer::dealer::Dealer;
+	use crate::error::error::BlackjackError;
+	use crate::money::money::Money;
+	use crate::outcome::outcome::*;
+	use crate::player::player::*;
+	use crate::rules::rules::*;
+
+	/// Stage of a round at the table
+	#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+	pub enum Phase {
+		/// Players are placing their wagers
+		Betting,
+		/// Bets are closed and the cards are about to be dealt
+		Dealing,
+		/// The dealer shows an Ace and players may insure their hands
+		Insurance,
+		/// The dealer may have blackjack and players may surrender before
+		/// the dealer checks
+		EarlySurrender,
+		/// The player in the given seat is playing the hand with the given index
+		PlayerTurn(usize, usize),
+		/// All players have acted and the dealer plays out their hand
+		DealerTurn,
+		/// The round is over and bets are waiting to be settled
+		Settlement
+	}
+
+	/// A blackjack table holding the shoe, the dealer and the seated players.
+	/// A round is driven through its phases by calling the methods below in
+	/// order; calls made in the wrong phase are rejected with an error.
+	pub struct Table {
+		deck: Deck,
+		dealer: Dealer,
+		seats: Vec<Player>,
+		bets: Vec<Vec<Money>>,
+		rules: Rules,
+		phase: Phase,
+		settlement: Vec<SettlementReport>
+	}
+
+	impl Table {
+		pub fn new(deck_count: usize, rules: Rules) -> Table {
+			let mut deck = Deck::new(deck_count);
+			deck.shuffle();
+			Table::with_deck(deck, rules)
+		}
+
+		/// Create a table dealing from the given shoe. The shoe is used as is
+		/// and only shuffled once its cut card comes out.
+		pub fn with_deck(deck: Deck, rules: Rules) -> Table {
+			Table {
+				deck,
+				dealer: Dealer::new(rules.clone()),
+				seats: Vec::new(),
+				bets: Vec::new(),
+				rules,
+				phase: Phase::Betting,
+				settlement: Vec::new()
+			}
+		}
+
+		pub fn get_phase(&self) -> Phase {
+			self.phase
+		}
+
+		pub fn get_rules(&self) -> &Rules {
+			&self.rules
+		}
+
+		pub fn get_deck(&self) -> &Deck {
+			&self.deck
+		}
+
+		/// Count the cards shown at the table with the given system. Hole
+		/// cards are only counted once they are turned over.
+		pub fn attach_counter(&mut self, system: Box<dyn CountingSystem>) {
+			self.deck.attach_counter(system);
+		}
+
+		/// Whether the shoe will be shuffled before the next deal
+		pub fn needs_shuffle(&self) -> bool {
+			self.deck.needs_shuffle()
+		}
+
+		/// Replace the dealer's drawing policy, which defaults to the one
+		/// selected by the table's rules
+		pub fn set_dealer_policy(&mut self, policy: Box<dyn DealerPolicy>) {
+			self.dealer.set_policy(policy);
+		}
+
+		/// Set the amount of money the house starts with
+		pub fn set_house_bankroll(&mut self, bankroll: Money) {
+			self.dealer.set_bankroll(bankroll);
+		}
+
+		pub fn get_dealer(&self) -> &Dealer {
+			&self.dealer
+		}
+
+		pub fn get_seat_count(&self) -> usize {
+			self.seats.len()
+		}
+
+		pub fn get_seat(&self, seat: usize) -> Result<&Player, BlackjackError> {
+			self.seats.get(seat).ok_or(BlackjackError::InvalidSeat(seat))
+		}
+
+		/// Total wagered by the player in the given seat this round
+		pub fn get_bet(&self, seat: usize) -> Result<Money, BlackjackError> {
+			Ok(self.get_spot_bets(seat)?.iter().sum())
+		}
+
+		/// Wagers on each of the spots played from the given seat
+		pub fn get_spot_bets(&self, seat: usize) -> Result<&[Money], BlackjackError> {
+			self.bets.get(seat).map(Vec::as_slice).ok_or(BlackjackError::InvalidSeat(seat))
+		}
+
+		/// Reports from the most recently settled round, one per seat
+		pub fn get_settlement(&self) -> &[SettlementReport] {
+			&self.settlement
+		}
+
+		/// Seat a new player at the table, applying the table's rules to them.
+		/// Returns the player's seat index.
+		pub fn add_player(&mut self, mut player: Player) -> Result<usize, BlackjackError> {
+			self.expect_phase(Phase::Betting)?;
+			player.set_rules(self.rules.clone());
+			self.seats.push(player);
+			self.bets.push(Vec::new());
+			Ok(self.seats.len() - 1)
+		}
+
+		/// Place the seat's wager for this round on a single spot, replacing
+		/// any wagers already placed
+		pub fn bet(&mut self, seat: usize, wager: Money) -> Result<(), BlackjackError> {
+			self.expect_phase(Phase::Betting)?;
+			self.check_seat(seat)?;
+			self.check_wager(seat, wager, Money::ZERO)?;
+			self.bets[seat] = vec![wager];
+			Ok(())
+		}
+
+		/// Place a wager on an additional spot for the player in the given seat.
+		/// Each spot is dealt and played as a separate hand.
+		pub fn add_spot(&mut self, seat: usize, wager: Money) -> Result<(), BlackjackError> {
+			self.expect_phase(Phase::Betting)?;
+			self.check_seat(seat)?;
+			if self.bets[seat].len() >= self.rules.max_spots {
+				return Err(BlackjackError::TooManySpots);
+			}
+			self.check_wager(seat, wager, self.get_bet(seat)?)?;
+			self.bets[seat].push(wager);
+			Ok(())
+		}
+
+		pub fn close_betting(&mut self) -> Result<(), BlackjackError> {
+			self.expect_phase(Phase::Betting)?;
+			if self.bets.iter().all(|bets| bets.is_empty()) {
+				return Err(BlackjackError::NoBets);
+			}
+			self.phase = Phase::Dealing;
+			Ok(())
+		}
+
+		/// Deal every player with a wager their starting hand, then the dealer.
+		/// The shoe is shuffled first if the cut card came out last round. If
+		/// dealing fails, no hands are kept and the table returns to betting.
+		pub fn deal(&mut self) -> Result<(), BlackjackError> {
+			self.expect_phase(Phase::Dealing)?;
+			if self.deck.needs_shuffle() {
+				self.deck.shuffle();
+			}
+			if let Err(err) = self.deal_hands() {
+				// Take back whatever was dealt so the bets aren't placed twice
+				self.void_round();
+				return Err(err);
+			}
+			if self.dealer.offers_insurance() {
+				self.phase = Phase::Insurance;
+			} else {
+				self.offer_early_surrender();
+			}
+			Ok(())
+		}
+
+		/// Call off the round in progress without settling it, for instance
+		/// when the shoe runs out mid-round. The cards are cleared, wagers that
+		/// haven't been settled are returned and betting reopens.
+		pub fn void_round(&mut self) {
+			for player in self.seats.iter_mut() {
+				player.clear_hands();
+			}
+			self.dealer.clear_hand();
+			self.deck.discard();
+			self.phase = Phase::Betting;
+		}
+
+		fn deal_hands(&mut self) -> Result<(), BlackjackError> {
+			for (player, bets) in self.seats.iter_mut().zip(self.bets.iter()) {
+				for &bet in bets {
+					player.bet(bet, &mut self.deck)?;
+				}
+			}
+			self.dealer.deal(&mut self.deck)
+		}
+
+		pub fn insure(&mut self, seat: usize, amount: Money) -> Result<(), BlackjackError> {
+			self.insure_spot(seat, 0, amount)
+		}
+
+		pub fn insure_spot(&mut self, seat: usize, spot: usize, amount: Money) -> Result<(), BlackjackError> {
+			self.expect_phase(Phase::Insurance)?;
+			self.check_seat(seat)?;
+			self.seats[seat].insure_spot(spot, amount)
+		}
+
+		pub fn take_even_money(&mut self, seat: usize) -> Result<(), BlackjackError> {
+			self.take_even_money_spot(seat, 0)
+		}
+
+		pub fn take_even_money_spot(&mut self, seat: usize, spot: usize) -> Result<(), BlackjackError> {
+			self.expect_phase(Phase::Insurance)?;
+			self.check_seat(seat)?;
+			self.seats[seat].take_even_money_spot(spot)
+		}
+
+		pub fn surrender_spot(&mut self, seat: usize, spot: usize) -> Result<(), BlackjackError> {
+			self.expect_phase(Phase::EarlySurrender)?;
+			self.check_seat(seat)?;
+			self.seats[seat].surrender_spot(spot)
+		}
+
+		pub fn close_insurance(&mut self) -> Result<(), BlackjackError> {
+			self.expect_phase(Phase::Insurance)?;
+			self.offer_early_surrender();
+			Ok(())
+		}
+
+		pub fn close_surrender(&mut self) -> Result<(), BlackjackError> {
+			self.expect_phase(Phase::EarlySurrender)?;
+			self.start_player_turns();
+			Ok(())
+		}
+
+		/// The player whose turn it is, if any
+		pub fn get_active_player(&self) -> Option<&Player> {
+			match self.phase {
+				Phase::PlayerTurn(seat, _) => Some(&self.seats[seat]),
+				_ => None
+			}
+		}
+
+		/// Actions available to the player in the given seat. During the
+		/// insurance and early surrender phases this is at most that action;
+		/// during player turns only the active seat has any actions.
+		pub fn legal_actions(&self, seat: usize) -> Vec<Action> {
+			let player = match self.seats.get(seat) {
+				Some(player) => player,
+				None => return Vec::new()
+			};
+			match self.phase {
+				Phase::Insurance if self.dealer.offers_insurance() && player.can_take_insurance() => vec![Action::Insurance],
+				Phase::EarlySurrender => player.legal_actions().into_iter().filter(|&action| action == Action::Surrender).collect(),
+				Phase::PlayerTurn(active, _) if active == seat => player.legal_actions(),
+				_ => Vec::new()
+			}
+		}
+
+		/// Perform an action for the player in the given seat if it is legal
+		pub fn apply(&mut self, seat: usize, action: Action) -> Result<(), BlackjackError> {
+			self.check_seat(seat)?;
+			match self.phase {
+				Phase::Insurance if action == Action::Insurance => (),
+				Phase::EarlySurrender if action == Action::Surrender => (),
+				Phase::PlayerTurn(active, _) if active == seat && action != Action::Insurance => (),
+				Phase::PlayerTurn(_, _) | Phase::Insurance | Phase::EarlySurrender => {
+					return Err(BlackjackError::IllegalAction(action))
+				},
+				phase => return Err(BlackjackError::WrongPhase(phase))
+			}
+			match action {
+				Action::Insurance if self.dealer.offers_insurance() => self.seats[seat].take_insurance()?,
+				Action::Insurance => return Err(BlackjackError::IllegalAction(action)),
+				_ => self.seats[seat].apply(action, &mut self.deck)?
+			}
+			if let Phase::PlayerTurn(_, _) = self.phase {
+				self.next_turn(seat);
+			}
+			Ok(())
+		}
+
+		pub fn hit(&mut self) -> Result<(), BlackjackError> {
+			self.apply_active(Action::Hit)
+		}
+
+		pub fn stand(&mut self) -> Result<(), BlackjackError> {
+			self.apply_active(Action::Stand)
+		}
+
+		pub fn double(&mut self) -> Result<(), BlackjackError> {
+			self.apply_active(Action::Double)
+		}
+
+		pub fn split(&mut self) -> Result<(), BlackjackError> {
+			self.apply_active(Action::Split)
+		}
+
+		pub fn surrender(&mut self) -> Result<(), BlackjackError> {
+			self.apply_active(Action::Surrender)
+		}
+
+		/// Play out the dealer's hand. The dealer only draws if at least one
+		/// player still has a live hand.
+		pub fn play_dealer(&mut self) -> Result<(), BlackjackError> {
+			self.expect_phase(Phase::DealerTurn)?;
+			if self.seats.iter().all(|player| player.has_lost()) {
+				self.dealer.reveal_hole_card(&mut self.deck)?;
+			} else {
+				self.dealer.play(&mut self.deck)?;
+			}
+			self.phase = Phase::Settlement;
+			Ok(())
+		}
+
+		/// Settle all bets against the house, clear the cards from the table
+		/// and open betting for the next round. Returns a settlement report for each seat.
+		pub fn settle(&mut self) -> Result<&[SettlementReport], BlackjackError> {
+			self.expect_phase(Phase::Settlement)?;
+			let dealer = self.dealer.outcome().ok_or(BlackjackError::NoActiveHand)?;
+			self.settlement = self.seats.iter_mut().map(|player| player.game_over(dealer)).collect();
+			self.dealer.game_over(&self.settlement);
+			self.deck.discard();
+			for bets in self.bets.iter_mut() {
+				bets.clear();
+			}
+			self.phase = Phase::Betting;
+			Ok(&self.settlement)
+		}
+
+		fn expect_phase(&self, phase: Phase) -> Result<(), BlackjackError> {
+			match self.phase == phase {
+				true => Ok(()),
+				false => Err(BlackjackError::WrongPhase(self.phase))
+			}
+		}
+
+		fn check_seat(&self, seat: usize) -> Result<(), BlackjackError> {
+			match seat < self.seats.len() {
+				true => Ok(()),
+				false => Err(BlackjackError::InvalidSeat(seat))
+			}
+		}
+
+		fn check_wager(&self, seat: usize, wager: Money, already_bet: Money) -> Result<(), BlackjackError> {
+			self.rules.check_bet(wager, already_bet)?;
+			let player = &self.seats[seat];
+			match already_bet.checked_add(wager) {
+				Some(total) if total > player.get_available_balance() => Err(BlackjackError::InsufficientFunds),
+				Some(total) if player.winnings_within_limit(total) => Ok(()),
+				_ => Err(BlackjackError::InvalidWager(wager))
+			}
+		}
+
+		fn apply_active(&mut self, action: Action) -> Result<(), BlackjackError> {
+			match self.phase {
+				Phase::PlayerTurn(seat, _) => self.apply(seat, action),
+				phase => Err(BlackjackError::WrongPhase(phase))
+			}
+		}
+
+		fn offer_early_surrender(&mut self) {
+			if self.rules.surrender == SurrenderRule::Early && self.dealer.should_peek() {
+				self.phase = Phase::EarlySurrender;
+			} else {
+				self.start_player_turns();
+			}
+		}
+
+		fn start_player_turns(&mut self) {
+			if self.dealer.peek_for_blackjack(&mut self.deck) {
+				self.phase = Phase::Settlement;
+			} else {
+				self.next_turn(0);
+			}
+		}
+
+		fn next_turn(&mut self, from_seat: usize) {
+			for seat in from_seat..self.seats.len() {
+				if let Some(hand) = self.seats[seat].get_playing_hand_index() {
+					self.phase = Phase::PlayerTurn(seat, hand);
+					return;
+				}
+			}
+			self.phase = Phase::DealerTurn;
+		}
+	}
+}
@@ -17,8 +17,12 @@ use std::io::Write;
 use std::process;
 
 extern crate blackjack;
-use blackjack::card::card::*;
+use blackjack::counting::counting::*;
+use blackjack::dealer::dealer::*;
+use blackjack::money::money::Money;
 use blackjack::player::player::*;
+use blackjack::rules::rules::*;
+use blackjack::table::table::*;
 
 fn get_int(prompt: &str) -> i32 {
         let mut input = String::new();
@@ -42,20 +46,89 @@ fn get_int(prompt: &str) -> i32 {
         }
 }
 
+fn get_money(prompt: &str) -> Money {
+	let mut input = String::new();
+	print!("{}", prompt);
+	io::stdout().flush().expect("Failed to flush");
+	match io::stdin().read_line(&mut input) {
+		Ok(_) => match input.trim().parse() {
+			Ok(amount) => amount,
+			Err(err) => {
+				println!("{}", err);
+				get_money(prompt)
+			}
+		},
+		Err(_) => {
+			println!("Failed to read");
+			get_money(prompt)
+		}
+	}
+}
+
+fn confirm(prompt: &str) -> bool {
+	let mut input = String::new();
+	print!("{}", prompt);
+	io::stdout().flush().expect("Failed to flush");
+	match io::stdin().read_line(&mut input) {
+		Ok(_) => matches!(input.trim(), "y" | "Y"),
+		Err(_) => false
+	}
+}
+
+fn offer_insurance(table: &mut Table, seat: usize, spot: usize) {
+	let player = table.get_seat(seat).expect("Invalid seat");
+	let name = player.get_name().to_string();
+	if spot >= player.get_hand_count() {
+		return;
+	}
+	if player.get_hand_at(spot).is_blackjack() {
+		if confirm(&format!("{} (spot {}): Take even money? [y/N]: ", name, spot + 1)) {
+			table.take_even_money_spot(seat, spot).expect("Even money refused");
+		}
+		return;
+	}
+	let max = table.get_spot_bets(seat).expect("Invalid seat")[spot].half();
+	loop {
+		let input = get_money(&format!("{} (spot {}): Insurance amount (0 to decline, up to {}): ", name, spot + 1, max));
+		if input == Money::ZERO || table.insure_spot(seat, spot, input).is_ok() {
+			break;
+		}
+		println!("Insurance must be between 0.01 and {}", max);
+	}
+}
+
 fn print_player_hand(player: &Player) {
+	let multiple_spots = player.get_spot_count() > 1;
 	for (ih, hand) in player.hand_iter().enumerate() {
-		println!("{}'s hand #{} ({}): {} points",
+		println!("{}'s hand #{}{} ({}): {} points",
 			player.get_name(),
 			ih + 1,
+			match multiple_spots {
+				true => format!(" on spot {}", hand.get_spot() + 1),
+				false => String::new()
+			},
 			match hand.get_is_set() {
 				true => "set",
 				false => "playing"
 			},
-			hand.value(false));
+			match hand.get_is_set() {
+				true => hand.value().to_string(),
+				false => hand.get_value().to_string()
+			});
+		for (ic, card) in hand.card_iter().enumerate() {
+			print!("{}{}", match ic { 0 => "", _ => ", " }, card);
+		}
+		println!();
+	}
+}
+
+fn print_dealer_hand(dealer: &Dealer) {
+	if let Some(hand) = dealer.get_hand() {
+		println!("Dealer's hand: {} points", hand.value());
 		for (ic, card) in hand.card_iter().enumerate() {
-			print!("{}{}", match ic { 0 => "", _ => ", " }, card.to_string());
+			print!("{}{}", match ic { 0 => "", _ => ", " }, card);
 		}
-		println!("");
+		println!();
 	}
 }
 
@@ -65,108 +138,152 @@ fn main() {
 	let deck_count = get_int("How many decks? ") as usize;
 	let player_count = get_int("How many players? ") as usize;
 
-	if (player_count + 1) * 5 >= deck_count * 52 {
+	let rules = Rules::default();
+	if (player_count * rules.max_spots + 1) * 5 >= deck_count * 52 {
 		println!("You need more decks to be able to play with this many players or the card supply might be exhausted.");
 		process::exit(1);
 	}
 
-	let mut deck = Deck::new(deck_count);
-	let mut players: Vec<Player> = Vec::with_capacity(player_count);
+	let mut table = Table::new(deck_count, rules);
 	for _ in 0..player_count {
 		let mut name = String::new();
 		print!("Enter your name: ");
 		io::stdout().flush().expect("Failed to flush");
 		io::stdin().read_line(&mut name).expect("Failed to read");
 		name.truncate(name.len() - 1);
-		let mut initial_balance = get_int("Enter player's initial balance: ");
-		if initial_balance <= 0 {
-			println!("Can't be negative. Defaulting to 1000.");
-			initial_balance = 1000;
+		let mut initial_balance = get_money("Enter player's initial balance: ");
+		if !initial_balance.is_positive() || initial_balance > Money::MAX {
+			println!("Must be positive and at most {}. Defaulting to 1000.", Money::MAX);
+			initial_balance = Money::from_units(1000);
 		}
-		let player = Player::new(name, false, initial_balance);
-		players.push(player);
+		table.add_player(Player::new(name, initial_balance)).expect("Failed to seat player");
 	}
 
-	let mut dealer = Player::new(String::from("Dealer"), true, -1);
+	if confirm("Show the Hi-Lo count between rounds? [y/N]: ") {
+		table.attach_counter(Box::new(HiLo));
+	}
 
 	loop {
-		deck.reset();
-		for player in players.iter_mut() {
-			let bet = loop {
-				let input = get_int(&format!("{}: Enter wager for this hand: ", player.get_name()));
-				if input <= 0 || input > player.get_balance() {
-					println!("Bet must be between 1 and your balance, {}", player.get_balance());
-				} else {
-					break input;
+		if let Some(counter) = table.get_deck().get_counter() {
+			println!("Running count {}, true count {:.1}, decks left: {}",
+				counter.get_running_count(), counter.get_true_count(), counter.get_remaining_decks());
+		}
+		for seat in 0..table.get_seat_count() {
+			loop {
+				let player = table.get_seat(seat).expect("Invalid seat");
+				let input = get_money(&format!("{}: Enter wager for this hand: ", player.get_name()));
+				match table.bet(seat, input) {
+					Ok(()) => break,
+					Err(err) => println!("{}", err)
 				}
-			};
-			player.bet(bet, &mut deck);
-		}
-		dealer.bet(0, &mut deck);
-
-		for player in players.iter_mut() {
-			print_player_hand(player);
-			while player.is_playing() {
-				let mut input = String::new();
-				print!("> ");
-				io::stdout().flush().expect("Failed to flush");
-				match io::stdin().read_line(&mut input) {
-					Ok(_) => match input.trim() {
-						"hit" => {
-							if !player.hit(&mut deck) {
-								print_player_hand(player);
-							}
-						},
-						"stand" => {
-							player.stand();
-							if player.is_playing() {
-								print_player_hand(player);
-							}
-						},
-						"surrender" => {
-							if !player.surrender() {
-								println!("Can't surrender this hand now.");
-							}
-						},
-						"split" => {
-							if player.split(&mut deck) {
-								print_player_hand(player);
-							} else {
-								println!("Can't split this hand");
+			}
+			while table.get_spot_bets(seat).expect("Invalid seat").len() < table.get_rules().max_spots &&
+				confirm(&format!("{}: Bet on another spot? [y/N]: ", table.get_seat(seat).expect("Invalid seat").get_name())) {
+				let input = get_money("Enter wager for this spot: ");
+				if let Err(err) = table.add_spot(seat, input) {
+					println!("{}", err);
+				}
+			}
+		}
+		table.close_betting().expect("No bets placed");
+		if table.needs_shuffle() {
+			println!("Shuffling the shoe");
+		}
+		if let Err(err) = table.deal() {
+			println!("{}. Bets are off, please bet again.", err);
+			continue;
+		}
+
+		if let Some(upcard) = table.get_dealer().get_upcard() {
+			println!("Dealer shows {}", upcard);
+		}
+		if table.get_phase() == Phase::Insurance {
+			for seat in 0..table.get_seat_count() {
+				for spot in 0..table.get_spot_bets(seat).expect("Invalid seat").len() {
+					offer_insurance(&mut table, seat, spot);
+				}
+			}
+			table.close_insurance().expect("Failed to close insurance");
+		}
+		if table.get_phase() == Phase::EarlySurrender {
+			for seat in 0..table.get_seat_count() {
+				for spot in 0..table.get_spot_bets(seat).expect("Invalid seat").len() {
+					let player = table.get_seat(seat).expect("Invalid seat");
+					if player.can_surrender_spot(spot) &&
+						confirm(&format!("{} (spot {}): Surrender? [y/N]: ", player.get_name(), spot + 1)) {
+						table.surrender_spot(seat, spot).expect("Surrender refused");
+					}
+				}
+			}
+			table.close_surrender().expect("Failed to close surrender");
+		}
+		if table.get_phase() == Phase::Settlement {
+			println!("Dealer has blackjack");
+			print_dealer_hand(table.get_dealer());
+		}
+
+		let mut current_seat = None;
+		while let Phase::PlayerTurn(seat, _) = table.get_phase() {
+			if current_seat != Some(seat) {
+				print_player_hand(table.get_seat(seat).expect("Invalid seat"));
+				current_seat = Some(seat);
+			}
+			let mut input = String::new();
+			print!("> ");
+			io::stdout().flush().expect("Failed to flush");
+			let accepted = match io::stdin().read_line(&mut input) {
+				Ok(_) => match input.trim() {
+					"help" => {
+						let actions: Vec<&str> = table.legal_actions(seat).iter().map(|action| action.name()).collect();
+						println!("Commands: {}", actions.join(", "));
+						false
+					},
+					command => match Action::from_name(command) {
+						Some(action) => match table.apply(seat, action) {
+							Ok(()) => true,
+							Err(err) => {
+								println!("{}", err);
+								false
 							}
 						},
-						"double" => {
-							player.double(&mut deck);
-						},
-						"help" => println!("Commands: hit, stand, surrender, split, double"),
-						_ => println!("Unknown command. Type 'help' for a list of available choices.")
-					},
-					Err(_) => println!("Failed to read")
+						None => {
+							println!("Unknown command. Type 'help' for a list of available choices.");
+							false
+						}
+					}
+				},
+				Err(_) => {
+					println!("Failed to read");
+					false
 				}
+			};
+			if accepted {
+				print_player_hand(table.get_seat(seat).expect("Invalid seat"));
 			}
-			print_player_hand(player);
 		}
-		let mut dealer_plays = false;
-		for player in players.iter() {
-			if !player.has_lost() {
-				dealer_plays = true;
-				break;
+
+		if table.get_phase() == Phase::DealerTurn {
+			println!("Dealer's turn");
+			if let Err(err) = table.play_dealer() {
+				println!("{}. The round is void, please bet again.", err);
+				table.void_round();
+				continue;
 			}
+			print_dealer_hand(table.get_dealer());
 		}
-		let dealer_value = match dealer_plays {
-			true => {
-				println!("Dealer's turn");
-				let value = dealer.play_as_dealer(&mut deck);
-				print_player_hand(&dealer);
-				value
-			},
-			false => 0
-		};
-		for player in players.iter_mut() {
-			player.game_over(dealer_value);
+		table.settle().expect("Failed to settle bets");
+		for (seat, report) in table.get_settlement().iter().enumerate() {
+			let player = table.get_seat(seat).expect("Invalid seat");
+			for (ih, hand) in report.hands.iter().enumerate() {
+				println!("{}'s hand #{} ({} points, wager {}): {} {:+}",
+					player.get_name(), ih + 1, hand.value, hand.wager, hand.outcome.name(), hand.payout);
+			}
+			if report.insurance.is_positive() {
+				println!("{}'s insurance: {:+}", player.get_name(), report.insurance_payout);
+			}
 			println!("{}'s balance, standing: {}/{}", player.get_name(), player.get_balance(), player.get_standing());
 		}
-		dealer.game_over(0);
+		println!("House standing: {}", table.get_dealer().get_standing());
 		let mut input = String::new();
 		print!("Play again? [Y/n]: ");
 		io::stdout().flush().expect("Failed to flush");
@@ -15,26 +15,58 @@
 #[cfg(test)]
 mod tests {
         use blackjack::card::card::Card;
-        use blackjack::card::card::Symbol;
+        use blackjack::card::card::Rank;
+        use blackjack::card::card::Suit;
         use blackjack::card::card::Deck;
+        use blackjack::counting::counting::*;
+        use blackjack::dealer::dealer::Dealer;
+        use blackjack::error::error::BlackjackError;
+        use blackjack::money::money::Money;
+        use blackjack::outcome::outcome::*;
+        use blackjack::player::player::{Action, Hand, HandValue, Player};
+        use blackjack::rules::rules::*;
+        use blackjack::table::table::*;
 
+        fn chips(units: i64) -> Money {
+                Money::from_units(units)
+        }
 
         #[test]
         fn card_to_int_works() {
-                let card = Card::new(2, Symbol::DIAMONDS);
+                let card = Card::new(Rank::Two, Suit::Diamonds);
                 assert_eq!(0b0001_0010, card.to_u32());
-                let card1 = Card::new(10, Symbol::SPADES);
+                let card1 = Card::new(Rank::Ten, Suit::Spades);
                 assert_eq!(0b1000_1010, card1.to_u32());
-                let card2 = Card::new(5, Symbol::HEARTS);
+                let card2 = Card::new(Rank::Five, Suit::Hearts);
                 assert_eq!(0b0010_0101, card2.to_u32());
-                let card3 = Card::new(7, Symbol::CLUBS);
+                let card3 = Card::new(Rank::Seven, Suit::Clubs);
                 assert_eq!(0b0100_0111, card3.to_u32());
         }
 
+        #[test]
+        fn card_notation_round_trips() {
+                let ace = Card::new(Rank::Ace, Suit::Spades);
+                assert_eq!(Ok(ace), "AS".parse());
+                assert_eq!(Ok(ace), "ace of spades".parse());
+                assert_eq!(Ok(Card::new(Rank::Ten, Suit::Hearts)), "10h".parse());
+                assert_eq!(Ok(Card::new(Rank::Ten, Suit::Hearts)), "TH".parse());
+                assert_eq!("Ace of Spades", ace.to_string());
+                assert_eq!("AS", ace.short_name());
+                assert_eq!(Err(BlackjackError::InvalidCard), "14S".parse::<Card>());
+                assert_eq!(Err(BlackjackError::InvalidCard), "Ace to Spades".parse::<Card>());
+                for card in Deck::create_valid_deck() {
+                        assert_eq!(Ok(card), Card::from_u32(card.to_u32()));
+                        assert_eq!(Ok(card), card.to_string().parse());
+                        assert_eq!(Ok(card), card.short_name().parse());
+                }
+                assert_eq!(Err(BlackjackError::InvalidCard), Card::from_u32(0b0001_1110));
+                assert!(Rank::Two < Rank::King);
+        }
+
         #[test]
         fn create_deck() {
                 let mut x = Deck::create_valid_deck();
-                x.sort_by(|a,b| a.to_u32().cmp(&b.to_u32()));
+                x.sort_by_key(|a| a.to_u32());
                 println!("{:?}", x);
                 assert_eq!(52,x.len())
         }
@@ -44,10 +76,1014 @@ mod tests {
                 let mut deck = Deck::new(4);
                 let mut counter = 0;
                 while counter < (4*52) {
-                        println!("{:08b}", deck.next_card().to_u32());
-                        counter += 1;assert_eq!(1,2);
+                        println!("{:08b}", deck.next_card().unwrap().to_u32());
+                        counter += 1;
                 }
                 dbg!(counter);
                 //Did not panic, yay!
+                assert_eq!(Some(BlackjackError::DeckExhausted), deck.next_card().err());
+        }
+
+        #[test]
+        fn shoe_reshuffles_discards() {
+                let mut deck = Deck::new(1);
+                deck.set_penetration(0.5);
+                assert_eq!(26, deck.get_cut_card());
+                for _ in 0..26 {
+                        deck.next_card().unwrap();
+                }
+                assert!(deck.needs_shuffle());
+                deck.discard();
+                for _ in 0..26 {
+                        deck.next_card().unwrap();
+                }
+                assert_eq!(0, deck.remaining());
+                let card = deck.next_card();
+                assert!(card.is_ok());
+                assert_eq!(25, deck.remaining());
+                assert!(deck.needs_shuffle());
+                deck.shuffle();
+                assert!(!deck.needs_shuffle());
+                assert_eq!(52, deck.remaining());
+        }
+
+        fn deal_all(deck: &mut Deck) -> Vec<u32> {
+                (0..52).map(|_| deck.next_card().unwrap().to_u32()).collect()
+        }
+
+        #[test]
+        fn seeded_shuffles_are_reproducible() {
+                let mut first = Deck::with_seed(1, 42);
+                let mut second = Deck::with_seed(1, 42);
+                first.shuffle();
+                second.shuffle();
+                assert_eq!(deal_all(&mut first), deal_all(&mut second));
+        }
+
+        fn assert_send<T: Send>() {}
+
+        #[test]
+        fn table_is_send() {
+                assert_send::<Deck>();
+                assert_send::<Dealer>();
+                assert_send::<Table>();
+        }
+
+        #[test]
+        fn shuffle_is_uniform() {
+                // Shuffle freshly built decks and count how often each card
+                // lands in each position. A uniform shuffle puts every card in
+                // every position equally often.
+                let factory_order = deal_all(&mut Deck::with_seed(1, 0));
+                let shuffles: u32 = 52 * 100;
+                let mut counts = vec![[0u32; 52]; 52];
+                for seed in 0..shuffles {
+                        let mut deck = Deck::with_seed(1, u64::from(seed));
+                        deck.shuffle();
+                        for (position, card) in deal_all(&mut deck).iter().enumerate() {
+                                let card = factory_order.iter().position(|c| c == card).unwrap();
+                                counts[card][position] += 1;
+                        }
+                }
+                let expected = f64::from(shuffles / 52);
+                let chi_squared: f64 = counts.iter()
+                        .flat_map(|row| row.iter())
+                        .map(|&count| (f64::from(count) - expected).powi(2) / expected)
+                        .sum();
+                // 99.9th percentile of the chi-squared distribution with
+                // 51 * 51 degrees of freedom
+                assert!(chi_squared < 2840.0, "chi-squared {}", chi_squared);
+        }
+
+        #[test]
+        fn stacked_deck_parsing() {
+                let mut deck = Deck::from_notation("AS kh 10d 2C").unwrap();
+                assert_eq!(4, deck.remaining());
+                assert_eq!("Ace of Spades", deck.next_card().unwrap().to_string());
+                assert_eq!("King of Hearts", deck.next_card().unwrap().to_string());
+                assert_eq!("Ten of Diamonds", deck.next_card().unwrap().to_string());
+                assert_eq!("Two of Clubs", deck.next_card().unwrap().to_string());
+                assert_eq!(0, deck.remaining());
+                assert_eq!(Some(BlackjackError::InvalidCard), Deck::from_notation("AS 1H").err());
+                assert_eq!(Some(BlackjackError::InvalidCard), Deck::from_notation("KX").err());
+        }
+
+        #[test]
+        fn dealer_busts_with_stacked_deck() {
+                // Players are dealt both of their cards before the dealer
+                let deck = Deck::from_notation("10S 9H 6D 10C 7H").unwrap();
+                let mut table = Table::with_deck(deck, Rules::new());
+                table.add_player(Player::new(String::from("Player"), chips(100))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                table.close_betting().unwrap();
+                table.deal().unwrap();
+                assert_eq!(Phase::PlayerTurn(0, 0), table.get_phase());
+                table.stand().unwrap();
+                table.play_dealer().unwrap();
+                assert!(table.get_dealer().has_busted());
+                table.settle().unwrap();
+                assert_eq!(chips(110), table.get_seat(0).unwrap().get_balance());
+        }
+
+        #[test]
+        fn hole_card_hidden_during_player_turns() {
+                let deck = Deck::from_notation("10S 9H 6D 10C 7H").unwrap();
+                let mut table = Table::with_deck(deck, Rules::new());
+                table.add_player(Player::new(String::from("Player"), chips(100))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                table.close_betting().unwrap();
+                table.deal().unwrap();
+                assert_eq!(Phase::PlayerTurn(0, 0), table.get_phase());
+                let hand = table.get_dealer().get_hand().unwrap();
+                assert_eq!(1, hand.get_card_count());
+                assert_eq!("6D", hand.get_card_at(0).short_name());
+                assert_eq!(6, hand.value());
+                assert!(table.get_dealer().get_hole_card().is_none());
+                assert_eq!(None, table.get_dealer().outcome());
+                table.stand().unwrap();
+                table.play_dealer().unwrap();
+                assert_eq!(Some(DealerOutcome::Bust), table.get_dealer().outcome());
+                let hand = table.get_dealer().get_hand().unwrap();
+                assert_eq!(3, hand.get_card_count());
+                assert_eq!("10C", hand.get_card_at(1).short_name());
+        }
+
+        #[test]
+        fn double_restrictions() {
+                assert!(DoubleRule::AnyTwo.allows(4));
+                assert!(DoubleRule::NineToEleven.allows(9));
+                assert!(!DoubleRule::NineToEleven.allows(12));
+                assert!(!DoubleRule::TenToEleven.allows(9));
+                assert!(DoubleRule::TenToEleven.allows(11));
+        }
+
+        #[test]
+        fn blackjack_payouts() {
+                assert_eq!(chips(15), BlackjackPayout::ThreeToTwo.pay(chips(10)));
+                assert_eq!(chips(12), BlackjackPayout::SixToFive.pay(chips(10)));
+                assert_eq!(chips(10), BlackjackPayout::OneToOne.pay(chips(10)));
+        }
+
+        #[test]
+        fn dealer_natural_beats_multi_card_21() {
+                // Without a hole card the player draws to 21 before the dealer's natural shows
+                let mut rules = Rules::new();
+                rules.hole_card = HoleCardRule::NoHoleCard;
+                let deck = Deck::from_notation("5S 6H KC 10D AD").unwrap();
+                let mut table = Table::with_deck(deck, rules);
+                table.add_player(Player::new(String::from("Player"), chips(100))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                table.close_betting().unwrap();
+                table.deal().unwrap();
+                table.hit().unwrap();
+                assert_eq!(21, table.get_seat(0).unwrap().get_hand_at(0).value());
+                table.stand().unwrap();
+                table.play_dealer().unwrap();
+                let report = table.settle().unwrap()[0].clone();
+                assert_eq!(DealerOutcome::Blackjack, report.dealer);
+                assert_eq!(HandOutcome::Lose, report.hands[0].outcome);
+                assert_eq!(chips(-10), report.hands[0].payout);
+                assert_eq!(chips(90), table.get_seat(0).unwrap().get_balance());
+        }
+
+        #[test]
+        fn player_natural_beats_multi_card_21() {
+                let deck = Deck::from_notation("AS KH 9C 5D 7H").unwrap();
+                let mut table = Table::with_deck(deck, Rules::new());
+                table.add_player(Player::new(String::from("Player"), chips(100))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                table.close_betting().unwrap();
+                table.deal().unwrap();
+                // The natural stands at once, so the dealer plays next
+                assert_eq!(Phase::DealerTurn, table.get_phase());
+                assert!(table.legal_actions(0).is_empty());
+                assert!(table.hit().is_err());
+                table.play_dealer().unwrap();
+                let report = table.settle().unwrap()[0].clone();
+                assert_eq!(DealerOutcome::Total(21), report.dealer);
+                assert_eq!(HandOutcome::Blackjack, report.hands[0].outcome);
+                assert_eq!(chips(15), report.hands[0].payout);
+                assert_eq!(chips(115), table.get_seat(0).unwrap().get_balance());
+        }
+
+        #[test]
+        fn hole_card_hidden_until_revealed() {
+                let mut deck = Deck::new(1);
+                let mut rules = Rules::new();
+                rules.hole_card = HoleCardRule::NoHoleCard;
+                let mut dealer = Dealer::new(rules);
+                dealer.deal(&mut deck).unwrap();
+                assert_eq!(1, dealer.get_hand().unwrap().get_card_count());
+                assert!(dealer.get_upcard().is_some());
+                assert!(dealer.get_hole_card().is_none());
+                assert!(!dealer.peek_for_blackjack(&mut deck));
+                dealer.reveal_hole_card(&mut deck).unwrap();
+                assert_eq!(2, dealer.get_hand().unwrap().get_card_count());
+                assert!(dealer.get_hole_card().is_some());
+        }
+
+        #[test]
+        fn only_upcard_visible_before_reveal() {
+                let mut deck = Deck::from_notation("9S 10H 5C").unwrap();
+                let mut dealer = Dealer::new(Rules::new());
+                dealer.deal(&mut deck).unwrap();
+                let hand = dealer.get_hand().unwrap();
+                assert_eq!(1, hand.get_card_count());
+                assert_eq!(Some(hand.get_card_at(0)), dealer.get_upcard());
+                assert_eq!(9, hand.value());
+                assert!(dealer.get_hole_card().is_none());
+                assert!(!dealer.peek_for_blackjack(&mut deck));
+                assert_eq!(1, dealer.get_hand().unwrap().get_card_count());
+                dealer.reveal_hole_card(&mut deck).unwrap();
+                assert_eq!(2, dealer.get_hand().unwrap().get_card_count());
+                assert_eq!(19, dealer.get_hand().unwrap().value());
+
+                // A natural stays hidden until the dealer peeks
+                let mut deck = Deck::from_notation("AS KH").unwrap();
+                let mut dealer = Dealer::new(Rules::new());
+                dealer.deal(&mut deck).unwrap();
+                assert!(!dealer.has_blackjack());
+                assert_eq!(None, dealer.outcome());
+                assert!(dealer.peek_for_blackjack(&mut deck));
+                assert!(dealer.has_blackjack());
+                assert_eq!(Some(DealerOutcome::Blackjack), dealer.outcome());
+        }
+
+        #[test]
+        fn insurance_limited_to_half_wager() {
+                let mut deck = Deck::new(1);
+                let mut player = Player::new(String::from("Player"), chips(100));
+                player.bet(chips(10), &mut deck).unwrap();
+                assert_eq!(Err(BlackjackError::InvalidWager(chips(6))), player.insure(chips(6)));
+                assert!(player.insure(chips(5)).is_ok());
+                assert_eq!(Err(BlackjackError::IllegalAction(Action::Insurance)), player.insure(chips(5)));
+                assert_eq!(chips(5), player.get_insurance());
+        }
+
+        #[test]
+        fn insurance_action_follows_dealer_upcard() {
+                // A player on their own never offers insurance
+                let mut deck = Deck::from_notation("10S 9H").unwrap();
+                let mut player = Player::new(String::from("Player"), chips(100));
+                player.bet(chips(10), &mut deck).unwrap();
+                assert!(!player.legal_actions().contains(&Action::Insurance));
+                assert_eq!(Err(BlackjackError::IllegalAction(Action::Insurance)), player.apply(Action::Insurance, &mut deck));
+
+                // Against an Ace the table insures each spot in turn for half its wager
+                let mut rules = Rules::new();
+                rules.max_spots = 2;
+                let deck = Deck::from_notation("10S 9H 10C 8D AD 9C").unwrap();
+                let mut table = Table::with_deck(deck, rules);
+                table.add_player(Player::new(String::from("Player"), chips(100))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                table.add_spot(0, chips(20)).unwrap();
+                table.close_betting().unwrap();
+                table.deal().unwrap();
+                assert_eq!(Phase::Insurance, table.get_phase());
+                assert_eq!(vec![Action::Insurance], table.legal_actions(0));
+                table.apply(0, Action::Insurance).unwrap();
+                table.apply(0, Action::Insurance).unwrap();
+                assert_eq!(chips(15), table.get_seat(0).unwrap().get_insurance());
+                assert_eq!(chips(10), table.get_seat(0).unwrap().get_hand_at(1).get_insurance());
+                assert!(table.legal_actions(0).is_empty());
+                assert_eq!(Err(BlackjackError::IllegalAction(Action::Insurance)), table.apply(0, Action::Insurance));
+                table.close_insurance().unwrap();
+                assert!(!table.legal_actions(0).contains(&Action::Insurance));
+                assert_eq!(Err(BlackjackError::IllegalAction(Action::Insurance)), table.apply(0, Action::Insurance));
+        }
+
+        #[test]
+        fn table_rejects_out_of_phase_calls() {
+                let mut table = Table::new(2, Rules::new());
+                table.add_player(Player::new(String::from("Player"), chips(100))).unwrap();
+                assert_eq!(Err(BlackjackError::WrongPhase(Phase::Betting)), table.hit());
+                assert_eq!(Err(BlackjackError::WrongPhase(Phase::Betting)), table.deal());
+                assert_eq!(Err(BlackjackError::NoBets), table.close_betting());
+                assert_eq!(Err(BlackjackError::InsufficientFunds), table.bet(0, chips(101)));
+                assert_eq!(Err(BlackjackError::InvalidWager(chips(-5))), table.bet(0, chips(-5)));
+                assert_eq!(Err(BlackjackError::InvalidSeat(1)), table.bet(1, chips(10)));
+                assert_eq!(Err(BlackjackError::InvalidSeat(1)), table.get_bet(1));
+                assert!(table.get_seat(1).is_err());
+                assert!(table.get_spot_bets(1).is_err());
+                assert!(table.bet(0, chips(10)).is_ok());
+                assert!(table.close_betting().is_ok());
+                assert_eq!(Phase::Dealing, table.get_phase());
+                assert!(table.bet(0, chips(10)).is_err());
+                assert!(table.settle().is_err());
+                assert!(table.deal().is_ok());
+                if table.get_phase() == Phase::Insurance {
+                        assert!(table.stand().is_err());
+                        assert!(table.close_insurance().is_ok());
+                }
+                while let Phase::PlayerTurn(seat, _) = table.get_phase() {
+                        assert_eq!(0, seat);
+                        assert!(table.play_dealer().is_err());
+                        assert!(table.stand().is_ok());
+                }
+                if table.get_phase() == Phase::DealerTurn {
+                        assert!(table.play_dealer().is_ok());
+                }
+                assert_eq!(Phase::Settlement, table.get_phase());
+                assert!(table.settle().is_ok());
+                assert_eq!(Phase::Betting, table.get_phase());
+                assert_eq!(0, table.get_seat(0).unwrap().get_hand_count());
+        }
+
+        #[test]
+        fn failed_deal_returns_to_betting() {
+                // The shoe runs out while dealing the second player
+                let deck = Deck::from_notation("10S 9H 8S").unwrap();
+                let mut table = Table::with_deck(deck, Rules::new());
+                table.add_player(Player::new(String::from("Alice"), chips(100))).unwrap();
+                table.add_player(Player::new(String::from("Bob"), chips(100))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                table.bet(1, chips(10)).unwrap();
+                table.close_betting().unwrap();
+                assert_eq!(Err(BlackjackError::DeckExhausted), table.deal());
+                assert_eq!(Phase::Betting, table.get_phase());
+                for seat in 0..2 {
+                        assert_eq!(0, table.get_seat(seat).unwrap().get_hand_count());
+                        assert_eq!(chips(100), table.get_seat(seat).unwrap().get_available_balance());
+                }
+                assert!(table.get_dealer().get_hand().is_none());
+
+                // Retrying doesn't take the first player's wager twice
+                table.close_betting().unwrap();
+                assert!(table.deal().is_err());
+                assert_eq!(0, table.get_seat(0).unwrap().get_hand_count());
+                assert_eq!(chips(100), table.get_seat(0).unwrap().get_available_balance());
+        }
+
+        #[test]
+        fn voided_round_returns_wagers() {
+                let deck = Deck::from_notation("10S 6H 9D 7C").unwrap();
+                let mut table = Table::with_deck(deck, Rules::new());
+                table.add_player(Player::new(String::from("Player"), chips(100))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                table.close_betting().unwrap();
+                table.deal().unwrap();
+                table.stand().unwrap();
+                // The dealer's 16 needs a card the shoe doesn't have
+                assert_eq!(Err(BlackjackError::DeckExhausted), table.play_dealer());
+                table.void_round();
+                assert_eq!(Phase::Betting, table.get_phase());
+                assert_eq!(0, table.get_seat(0).unwrap().get_hand_count());
+                assert_eq!(chips(100), table.get_seat(0).unwrap().get_balance());
+                assert!(table.get_dealer().get_hand().is_none());
+        }
+
+        #[test]
+        fn legal_actions_follow_hand_state() {
+                let mut deck = Deck::new(1);
+                let mut player = Player::new(String::from("Player"), chips(15));
+                assert!(player.legal_actions().is_empty());
+                player.bet(chips(10), &mut deck).unwrap();
+                let actions = player.legal_actions();
+                assert!(actions.contains(&Action::Hit));
+                assert!(actions.contains(&Action::Stand));
+                assert!(!actions.contains(&Action::Double));
+                assert!(!actions.contains(&Action::Split));
+                assert!(player.apply(Action::Stand, &mut deck).is_ok());
+                assert!(player.legal_actions().is_empty());
+                assert_eq!(Err(BlackjackError::NoActiveHand), player.apply(Action::Hit, &mut deck));
+                for action in &[Action::Hit, Action::Stand, Action::Double, Action::Split, Action::Surrender, Action::Insurance] {
+                        assert_eq!(Some(*action), Action::from_name(action.name()));
+                }
+        }
+
+        fn split_aces(hit_split_aces: bool, resplit_aces: bool) -> (Player, Deck) {
+                let mut rules = Rules::new();
+                rules.hit_split_aces = hit_split_aces;
+                rules.resplit_aces = resplit_aces;
+                let mut deck = Deck::from_notation("AS AH KD AC 5C 6C").unwrap();
+                let mut player = Player::with_rules(String::from("Player"), chips(100), rules);
+                player.bet(chips(10), &mut deck).unwrap();
+                player.split(&mut deck).unwrap();
+                (player, deck)
+        }
+
+        #[test]
+        fn split_aces_rules() {
+                // One card each, no resplitting: both hands stand immediately
+                let (player, _) = split_aces(false, false);
+                assert_eq!(None, player.get_playing_hand_index());
+                assert_eq!(21, player.get_hand_at(0).value());
+                assert!(!player.get_hand_at(0).is_blackjack());
+                assert!(!player.has_blackjack());
+
+                // The second pair of aces may only be resplit or stood on
+                let (mut player, mut deck) = split_aces(false, true);
+                assert_eq!(Some(1), player.get_playing_hand_index());
+                let actions = player.legal_actions();
+                assert!(actions.contains(&Action::Split));
+                assert!(!actions.contains(&Action::Hit));
+                assert!(!actions.contains(&Action::Double));
+                assert_eq!(Err(BlackjackError::IllegalAction(Action::Hit)), player.hit(&mut deck).map(|_| ()));
+                player.split(&mut deck).unwrap();
+                assert_eq!(3, player.get_hand_count());
+                assert_eq!(None, player.get_playing_hand_index());
+
+                // Once the hand limit rules out a resplit, the aces stand
+                let mut rules = Rules::new();
+                rules.resplit_aces = true;
+                rules.max_hands = 2;
+                let mut deck = Deck::from_notation("AS AH KD AC").unwrap();
+                let mut player = Player::with_rules(String::from("Player"), chips(100), rules);
+                player.bet(chips(10), &mut deck).unwrap();
+                player.split(&mut deck).unwrap();
+                assert_eq!(None, player.get_playing_hand_index());
+                assert!(player.legal_actions().is_empty());
+
+                // Split aces play like any other hand but can't be resplit
+                let (player, _) = split_aces(true, false);
+                assert_eq!(Some(0), player.get_playing_hand_index());
+                let actions = player.legal_actions();
+                assert!(actions.contains(&Action::Hit));
+                assert!(actions.contains(&Action::Double));
+                assert!(!player.get_hand_at(1).can_split(player.get_rules()));
+
+                let (mut player, mut deck) = split_aces(true, true);
+                player.stand().unwrap();
+                let actions = player.legal_actions();
+                assert!(actions.contains(&Action::Hit));
+                assert!(actions.contains(&Action::Split));
+                assert!(player.hit(&mut deck).is_ok());
+        }
+
+        #[test]
+        fn split_by_rank_or_value() {
+                let mut rules = Rules::new();
+                let king = Card::new(Rank::King, Suit::Spades);
+                let queen = Card::new(Rank::Queen, Suit::Hearts);
+                assert!(rules.split.allows(&king, &king));
+                assert!(!rules.split.allows(&king, &queen));
+                let mut deck = Deck::from_notation("KS QH").unwrap();
+                let mut player = Player::with_rules(String::from("Player"), chips(100), rules.clone());
+                player.bet(chips(10), &mut deck).unwrap();
+                assert!(!player.legal_actions().contains(&Action::Split));
+                rules.split = SplitRule::SameValue;
+                player.set_rules(rules);
+                assert!(player.legal_actions().contains(&Action::Split));
+        }
+
+        #[test]
+        fn resplits_limited_by_max_hands() {
+                let mut rules = Rules::new();
+                rules.max_hands = 3;
+                let mut deck = Deck::from_notation("8S 8H 8D 8C 8S 8H 2C 3C").unwrap();
+                let mut player = Player::with_rules(String::from("Player"), chips(100), rules);
+                player.bet(chips(10), &mut deck).unwrap();
+                player.split(&mut deck).unwrap();
+                player.split(&mut deck).unwrap();
+                assert_eq!(3, player.get_hand_count());
+                assert!(player.get_hand_at(0).can_split(player.get_rules()));
+                assert!(!player.legal_actions().contains(&Action::Split));
+                assert_eq!(Err(BlackjackError::IllegalAction(Action::Split)), player.split(&mut deck));
+        }
+
+        #[test]
+        fn double_only_where_rules_allow() {
+                // Doubling is refused after drawing a third card
+                let mut deck = Deck::from_notation("5S 6H 2D 9C").unwrap();
+                let mut player = Player::new(String::from("Player"), chips(100));
+                player.bet(chips(10), &mut deck).unwrap();
+                player.hit(&mut deck).unwrap();
+                assert!(!player.legal_actions().contains(&Action::Double));
+                assert_eq!(Err(BlackjackError::IllegalAction(Action::Double)), player.double(&mut deck).map(|_| ()));
+
+                // Reno rule: only 9, 10 or 11
+                let mut rules = Rules::new();
+                rules.double = DoubleRule::NineToEleven;
+                let mut deck = Deck::from_notation("10S 2H").unwrap();
+                let mut player = Player::with_rules(String::from("Player"), chips(100), rules.clone());
+                player.bet(chips(10), &mut deck).unwrap();
+                assert_eq!(Err(BlackjackError::IllegalAction(Action::Double)), player.double(&mut deck).map(|_| ()));
+
+                // No doubling after splitting
+                rules.double_after_split = false;
+                let mut deck = Deck::from_notation("5S 5H 5D 6C").unwrap();
+                let mut player = Player::with_rules(String::from("Player"), chips(100), rules);
+                player.bet(chips(10), &mut deck).unwrap();
+                player.split(&mut deck).unwrap();
+                assert_eq!(10, player.get_hand_at(0).value());
+                assert!(!player.legal_actions().contains(&Action::Double));
+
+                // A double that busts still succeeds
+                let mut deck = Deck::from_notation("6S 6H KD").unwrap();
+                let mut player = Player::new(String::from("Player"), chips(100));
+                player.bet(chips(10), &mut deck).unwrap();
+                let card = player.double(&mut deck).unwrap();
+                assert_eq!(Rank::King, card.rank);
+                assert_eq!(chips(20), player.get_hand_at(0).get_wager());
+                assert!(player.has_busted());
+        }
+
+        fn surrender_against_blackjack(rules: Rules) -> Table {
+                // The dealer shows an Ace with a King in the hole
+                let deck = Deck::from_notation("10S 6H AD KC").unwrap();
+                let mut table = Table::with_deck(deck, rules);
+                table.add_player(Player::new(String::from("Player"), chips(100))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                table.close_betting().unwrap();
+                table.deal().unwrap();
+                table.close_insurance().unwrap();
+                table
+        }
+
+        #[test]
+        fn surrender_modes() {
+                let mut rules = Rules::new();
+                rules.surrender = SurrenderRule::Early;
+                let mut table = surrender_against_blackjack(rules.clone());
+                assert_eq!(Phase::EarlySurrender, table.get_phase());
+                assert_eq!(vec![Action::Surrender], table.legal_actions(0));
+                assert_eq!(Err(BlackjackError::IllegalAction(Action::Hit)), table.apply(0, Action::Hit));
+                table.apply(0, Action::Surrender).unwrap();
+                table.close_surrender().unwrap();
+                assert_eq!(Phase::Settlement, table.get_phase());
+                table.settle().unwrap();
+                assert_eq!(chips(95), table.get_seat(0).unwrap().get_balance());
+
+                // Late surrender at a no-hole-card table loses everything to a blackjack
+                rules.surrender = SurrenderRule::Late;
+                rules.hole_card = HoleCardRule::NoHoleCard;
+                let mut table = surrender_against_blackjack(rules.clone());
+                assert_eq!(Phase::PlayerTurn(0, 0), table.get_phase());
+                table.surrender().unwrap();
+                table.play_dealer().unwrap();
+                assert!(table.get_dealer().has_blackjack());
+                table.settle().unwrap();
+                assert_eq!(chips(90), table.get_seat(0).unwrap().get_balance());
+
+                rules.surrender = SurrenderRule::None;
+                let mut table = surrender_against_blackjack(rules);
+                assert!(!table.legal_actions(0).contains(&Action::Surrender));
+                assert_eq!(Err(BlackjackError::IllegalAction(Action::Surrender)), table.surrender());
+        }
+
+        #[test]
+        fn early_surrender_per_spot() {
+                // Only the second spot surrenders; the first plays on and loses to the blackjack
+                let mut rules = Rules::new();
+                rules.surrender = SurrenderRule::Early;
+                rules.max_spots = 2;
+                let deck = Deck::from_notation("10S 7H 10C 6D AD KC").unwrap();
+                let mut table = Table::with_deck(deck, rules);
+                table.add_player(Player::new(String::from("Player"), chips(100))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                table.add_spot(0, chips(20)).unwrap();
+                table.close_betting().unwrap();
+                table.deal().unwrap();
+                assert_eq!(Err(BlackjackError::WrongPhase(Phase::Insurance)), table.surrender_spot(0, 1));
+                table.close_insurance().unwrap();
+                assert_eq!(Phase::EarlySurrender, table.get_phase());
+                assert!(table.get_seat(0).unwrap().can_surrender_spot(1));
+                table.surrender_spot(0, 1).unwrap();
+                assert!(!table.get_seat(0).unwrap().can_surrender_spot(1));
+                assert_eq!(Err(BlackjackError::IllegalAction(Action::Surrender)), table.surrender_spot(0, 1));
+                assert!(table.get_seat(0).unwrap().can_surrender_spot(0));
+                table.close_surrender().unwrap();
+                assert_eq!(Phase::Settlement, table.get_phase());
+                table.settle().unwrap();
+                assert_eq!(chips(80), table.get_seat(0).unwrap().get_balance());
+        }
+
+        #[test]
+        fn surrender_after_split() {
+                let mut rules = Rules::new();
+                let mut deck = Deck::from_notation("8S 8H 2D 3C").unwrap();
+                let mut player = Player::with_rules(String::from("Player"), chips(100), rules.clone());
+                player.bet(chips(10), &mut deck).unwrap();
+                assert!(player.legal_actions().contains(&Action::Surrender));
+                player.split(&mut deck).unwrap();
+                assert!(!player.legal_actions().contains(&Action::Surrender));
+                assert_eq!(Err(BlackjackError::IllegalAction(Action::Surrender)), player.surrender());
+                rules.surrender_after_split = true;
+                player.set_rules(rules);
+                assert!(player.surrender().is_ok());
+        }
+
+        struct HitsSoft18;
+
+        impl DealerPolicy for HitsSoft18 {
+                fn hits(&self, value: u32, soft: bool) -> bool {
+                        value < 17 || (value <= 18 && soft)
+                }
+        }
+
+        fn dealer_total(cards: &str, policy: Box<dyn DealerPolicy>) -> DealerOutcome {
+                let mut deck = Deck::from_notation(cards).unwrap();
+                let mut dealer = Dealer::new(Rules::new());
+                dealer.set_policy(policy);
+                dealer.deal(&mut deck).unwrap();
+                dealer.play(&mut deck).unwrap()
+        }
+
+        #[test]
+        fn dealer_policies() {
+                assert_eq!(DealerOutcome::Total(17), dealer_total("AS 6H 2C", Box::new(StandsOnSoft17)));
+                assert_eq!(DealerOutcome::Total(19), dealer_total("AS 6H 2C", Box::new(HitsSoft17)));
+                assert_eq!(DealerOutcome::Total(17), dealer_total("10S 7H 2C", Box::new(HitsSoft17)));
+                assert_eq!(DealerOutcome::Total(18), dealer_total("AS 7H 2C", Box::new(HitsSoft17)));
+                assert_eq!(DealerOutcome::Total(20), dealer_total("AS 7H 2C", Box::new(HitsSoft18)));
+                assert_eq!(DealerOutcome::Bust, dealer_total("10S 6H KC", Box::new(StandsOnSoft17)));
+                assert_eq!(DealerOutcome::Blackjack, dealer_total("AS KH", Box::new(HitsSoft17)));
+
+                let mut rules = Rules::new();
+                rules.dealer_hits_soft_17 = true;
+                let mut deck = Deck::from_notation("AS 6H 2C").unwrap();
+                let mut dealer = Dealer::new(rules);
+                dealer.deal(&mut deck).unwrap();
+                assert_eq!(DealerOutcome::Total(19), dealer.play(&mut deck).unwrap());
+        }
+
+        #[test]
+        fn hand_outcomes() {
+                // Split eights: one hand busts, the other stands on 18
+                let mut deck = Deck::from_notation("8S 8H 10D QC 5S").unwrap();
+                let mut player = Player::new(String::from("Player"), chips(100));
+                player.bet(chips(10), &mut deck).unwrap();
+                player.split(&mut deck).unwrap();
+                player.stand().unwrap();
+                assert!(!player.has_busted());
+                player.hit(&mut deck).unwrap();
+                assert!(player.get_hand_at(1).busted());
+                assert!(!player.has_busted());
+                assert_eq!(18, player.first_hand_value());
+                let report = player.game_over(DealerOutcome::Total(17));
+                let outcomes: Vec<HandOutcome> = report.hands.iter().map(|hand| hand.outcome).collect();
+                assert_eq!(vec![HandOutcome::Win, HandOutcome::Lose], outcomes);
+                assert_eq!(chips(100), player.get_balance());
+
+                let rules = Rules::new();
+                let mut deck = Deck::from_notation("AS KH 10S 7H 2C 3D").unwrap();
+                let blackjack = Hand::new(chips(10), &mut deck).unwrap();
+                let seventeen = Hand::new(chips(10), &mut deck).unwrap();
+                let five = Hand::new(chips(10), &mut deck).unwrap();
+                assert_eq!(HandOutcome::Blackjack, blackjack.outcome(DealerOutcome::Total(21), &rules));
+                assert_eq!(HandOutcome::Push, blackjack.outcome(DealerOutcome::Blackjack, &rules));
+                assert_eq!(HandOutcome::Push, seventeen.outcome(DealerOutcome::Total(17), &rules));
+                assert_eq!(HandOutcome::Lose, seventeen.outcome(DealerOutcome::Blackjack, &rules));
+                // A bust dealer is not a dealer total of 0
+                assert_eq!(HandOutcome::Win, five.outcome(DealerOutcome::Bust, &rules));
+                assert_eq!(HandOutcome::Lose, five.outcome(DealerOutcome::Total(17), &rules));
+        }
+
+        #[test]
+        fn settlement_report() {
+                // Player doubles 11 into 21 and insures against the dealer's Ace
+                let deck = Deck::from_notation("6S 5H AD 9C 10S").unwrap();
+                let mut table = Table::with_deck(deck, Rules::new());
+                table.add_player(Player::new(String::from("Player"), chips(100))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                table.close_betting().unwrap();
+                table.deal().unwrap();
+                table.insure(0, chips(5)).unwrap();
+                table.close_insurance().unwrap();
+                table.double().unwrap();
+                table.play_dealer().unwrap();
+                let report = table.settle().unwrap()[0].clone();
+                assert_eq!(DealerOutcome::Total(20), report.dealer);
+                assert_eq!(1, report.hands.len());
+                let hand = &report.hands[0];
+                assert_eq!(3, hand.cards.len());
+                assert_eq!(21, hand.value);
+                assert_eq!(chips(20), hand.wager);
+                assert_eq!(HandOutcome::Win, hand.outcome);
+                assert_eq!(chips(20), hand.payout);
+                assert_eq!(chips(-5), report.insurance_payout);
+                assert_eq!(chips(15), report.net());
+                assert_eq!(chips(115), report.balance);
+                assert_eq!(chips(115), table.get_seat(0).unwrap().get_balance());
+                assert_eq!(&report, &table.get_settlement()[0]);
+        }
+
+        fn settle_against_ace(cards: &str, even_money: bool) -> SettlementReport {
+                let deck = Deck::from_notation(cards).unwrap();
+                let mut table = Table::with_deck(deck, Rules::new());
+                table.add_player(Player::new(String::from("Player"), chips(100))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                table.close_betting().unwrap();
+                table.deal().unwrap();
+                assert_eq!(Phase::Insurance, table.get_phase());
+                match even_money {
+                        true => table.take_even_money(0).unwrap(),
+                        false => table.insure(0, chips(5)).unwrap()
+                }
+                table.close_insurance().unwrap();
+                if let Phase::PlayerTurn(_, _) = table.get_phase() {
+                        table.stand().unwrap();
+                }
+                if table.get_phase() == Phase::DealerTurn {
+                        table.play_dealer().unwrap();
+                }
+                table.settle().unwrap()[0].clone()
+        }
+
+        #[test]
+        fn insurance_settlement() {
+                // Insurance pays 2:1 when the dealer has blackjack, covering the lost hand
+                let report = settle_against_ace("10S 9H AD KC", false);
+                assert_eq!(DealerOutcome::Blackjack, report.dealer);
+                assert_eq!(chips(-10), report.hands[0].payout);
+                assert_eq!(chips(10), report.insurance_payout);
+                assert_eq!(chips(100), report.balance);
+
+                // Otherwise the insurance bet is lost
+                let report = settle_against_ace("10S 9H AD 7C", false);
+                assert_eq!(DealerOutcome::Total(18), report.dealer);
+                assert_eq!(chips(10), report.hands[0].payout);
+                assert_eq!(chips(-5), report.insurance_payout);
+                assert_eq!(chips(105), report.balance);
+        }
+
+        #[test]
+        fn even_money_settlement() {
+                // Even money pays the natural 1:1 whether or not the dealer has blackjack
+                for cards in &["AS KH AD 7C", "AS KH AD KC"] {
+                        let report = settle_against_ace(cards, true);
+                        assert_eq!(HandOutcome::Win, report.hands[0].outcome);
+                        assert_eq!(chips(10), report.hands[0].payout);
+                        assert_eq!(Money::ZERO, report.insurance_payout);
+                        assert_eq!(chips(110), report.balance);
+                }
+        }
+
+        #[test]
+        fn multiple_spots() {
+                let mut rules = Rules::new();
+                rules.max_spots = 2;
+                let deck = Deck::from_notation("8S 8H 10C 9D AD 7C 2S 3S").unwrap();
+                let mut table = Table::with_deck(deck, rules);
+                table.add_player(Player::new(String::from("Player"), chips(100))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                assert_eq!(Err(BlackjackError::InsufficientFunds), table.add_spot(0, chips(91)));
+                table.add_spot(0, chips(20)).unwrap();
+                assert_eq!(Err(BlackjackError::TooManySpots), table.add_spot(0, chips(20)));
+
+                // Players dealt directly are held to the same limit
+                let mut deck = Deck::new(1);
+                let mut player = Player::new(String::from("Player"), chips(100));
+                player.set_rules(table.get_rules().clone());
+                player.bet(chips(10), &mut deck).unwrap();
+                player.bet(chips(10), &mut deck).unwrap();
+                assert_eq!(Err(BlackjackError::TooManySpots), player.bet(chips(10), &mut deck));
+                assert_eq!(2, player.get_spot_count());
+
+                assert_eq!(&[chips(10), chips(20)], table.get_spot_bets(0).unwrap());
+                assert_eq!(chips(30), table.get_bet(0).unwrap());
+                table.close_betting().unwrap();
+                table.deal().unwrap();
+                assert_eq!(2, table.get_seat(0).unwrap().get_spot_count());
+
+                // Each spot is insured separately
+                assert_eq!(Err(BlackjackError::InvalidWager(chips(11))), table.insure_spot(0, 1, chips(11)));
+                table.insure_spot(0, 1, chips(10)).unwrap();
+                table.close_insurance().unwrap();
+
+                // Hands split from the first spot are played before the second spot
+                assert_eq!(Phase::PlayerTurn(0, 0), table.get_phase());
+                table.split().unwrap();
+                let spots: Vec<usize> = table.get_seat(0).unwrap().hand_iter().map(|hand| hand.get_spot()).collect();
+                assert_eq!(vec![0, 0, 1], spots);
+                table.stand().unwrap();
+                assert_eq!(Phase::PlayerTurn(0, 1), table.get_phase());
+                table.stand().unwrap();
+                assert_eq!(Phase::PlayerTurn(0, 2), table.get_phase());
+                table.stand().unwrap();
+                table.play_dealer().unwrap();
+                let report = table.settle().unwrap()[0].clone();
+                let payouts: Vec<Money> = report.hands.iter().map(|hand| hand.payout).collect();
+                assert_eq!(vec![chips(-10), chips(-10), chips(20)], payouts);
+                assert_eq!(chips(-10), report.insurance_payout);
+                assert_eq!(chips(90), report.balance);
+        }
+
+        #[test]
+        fn house_bankroll() {
+                let deck = Deck::from_notation("10S 9H 7D 10C 10H 7S 5D").unwrap();
+                let mut table = Table::with_deck(deck, Rules::new());
+                table.set_house_bankroll(chips(1000));
+                table.add_player(Player::new(String::from("Alice"), chips(100))).unwrap();
+                table.add_player(Player::new(String::from("Bob"), chips(100))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                table.bet(1, chips(30)).unwrap();
+                table.close_betting().unwrap();
+                table.deal().unwrap();
+                table.stand().unwrap();
+                table.hit().unwrap();
+                table.play_dealer().unwrap();
+                // Alice's 19 beats the dealer's 17 while Bob busts
+                assert_eq!(Some(DealerOutcome::Total(17)), table.get_dealer().outcome());
+                table.settle().unwrap();
+                assert!(table.get_dealer().get_hand().is_none());
+                assert_eq!(chips(20), table.get_dealer().get_standing());
+                assert_eq!(chips(1020), table.get_dealer().get_bankroll());
+                assert_eq!(chips(110), table.get_seat(0).unwrap().get_balance());
+                assert_eq!(chips(70), table.get_seat(1).unwrap().get_balance());
+        }
+
+        #[test]
+        fn wagers_limited_by_bankroll() {
+                let mut deck = Deck::new(1);
+                let mut player = Player::new(String::from("Player"), chips(15));
+                assert_eq!(Err(BlackjackError::InsufficientFunds), player.bet(chips(20), &mut deck));
+                player.bet(chips(10), &mut deck).unwrap();
+                assert_eq!(chips(5), player.get_available_balance());
+                assert!(player.insure(chips(5)).is_ok());
+                assert_eq!(chips(0), player.get_available_balance());
+                assert!(!player.legal_actions().contains(&Action::Double));
+                assert_eq!(Err(BlackjackError::InsufficientFunds), player.double(&mut deck).map(|_| ()));
+
+                // An unsplittable hand is refused as illegal before the bankroll is checked
+                let mut deck = Deck::from_notation("10S 9H 8S 8H").unwrap();
+                let mut player = Player::new(String::from("Player"), chips(10));
+                player.bet(chips(10), &mut deck).unwrap();
+                assert_eq!(Err(BlackjackError::IllegalAction(Action::Split)), player.split(&mut deck));
+                let mut player = Player::new(String::from("Player"), chips(10));
+                player.bet(chips(10), &mut deck).unwrap();
+                assert_eq!(Err(BlackjackError::InsufficientFunds), player.split(&mut deck));
+        }
+
+        #[test]
+        fn double_for_less() {
+                let mut rules = Rules::new();
+                rules.double_for_less = true;
+                let deck = Deck::from_notation("6S 5H 10D 7C 10H").unwrap();
+                let mut table = Table::with_deck(deck, rules);
+                table.add_player(Player::new(String::from("Player"), chips(15))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                table.close_betting().unwrap();
+                table.deal().unwrap();
+                assert!(table.legal_actions(0).contains(&Action::Double));
+                table.double().unwrap();
+                assert_eq!(chips(15), table.get_seat(0).unwrap().get_hand_at(0).get_wager());
+                assert_eq!(Money::ZERO, table.get_seat(0).unwrap().get_available_balance());
+                table.play_dealer().unwrap();
+                let report = table.settle().unwrap()[0].clone();
+                assert_eq!(HandOutcome::Win, report.hands[0].outcome);
+                assert_eq!(chips(15), report.hands[0].payout);
+                assert_eq!(chips(30), table.get_seat(0).unwrap().get_balance());
+        }
+
+        #[test]
+        fn money_rounding() {
+                assert_eq!(Ok(Money::from_cents(1250)), "12.5".parse());
+                assert_eq!(Ok(Money::from_cents(-300)), "-3".parse());
+                assert_eq!(Err(BlackjackError::InvalidAmount), "1.234".parse::<Money>());
+                assert_eq!(Err(BlackjackError::InvalidAmount), "ten".parse::<Money>());
+                assert_eq!("12.50", Money::from_cents(1250).to_string());
+                assert_eq!("-0.05", Money::from_cents(-5).to_string());
+                assert_eq!("+1.00", format!("{:+}", chips(1)));
+                assert_eq!(None, Money::from_cents(i64::MAX).checked_add(Money::from_cents(1)));
+
+                // Fractions of a cent are rounded in the house's favour
+                let odd = Money::from_cents(25);
+                assert_eq!(Money::from_cents(12), odd.half());
+                assert_eq!(Money::from_cents(37), BlackjackPayout::ThreeToTwo.pay(odd));
+                assert_eq!(Money::from_cents(30), BlackjackPayout::SixToFive.pay(odd));
+
+                let mut deck = Deck::from_notation("10S 6H").unwrap();
+                let mut player = Player::new(String::from("Player"), chips(1));
+                player.bet(odd, &mut deck).unwrap();
+                assert_eq!(Err(BlackjackError::InvalidWager(Money::from_cents(13))), player.insure(Money::from_cents(13)));
+                player.surrender().unwrap();
+                assert_eq!(Money::from_cents(87), player.get_balance());
+                let report = player.game_over(DealerOutcome::Total(20));
+                assert_eq!(Money::from_cents(-13), report.net());
+                assert_eq!(Money::from_cents(87), player.get_balance());
+        }
+
+        #[test]
+        fn money_limits() {
+                assert_eq!(None, Money::checked_from_units(i64::MAX));
+                assert_eq!(Some(chips(5)), Money::checked_from_units(5));
+                assert!(Money::MAX.in_range());
+                assert!((-Money::MAX).in_range());
+                assert!(!(Money::MAX + Money::from_cents(1)).in_range());
+
+                // Bets that could win a player past Money::MAX are refused
+                let balance = Money::MAX - chips(10);
+                let mut table = Table::new(1, Rules::new());
+                table.add_player(Player::new(String::from("Player"), balance)).unwrap();
+                assert_eq!(Err(BlackjackError::InvalidWager(chips(10))), table.bet(0, chips(10)));
+                assert!(table.bet(0, chips(5)).is_ok());
+                let mut deck = Deck::new(1);
+                let mut player = Player::new(String::from("Player"), balance);
+                assert_eq!(Err(BlackjackError::InvalidWager(chips(6))), player.bet(chips(6), &mut deck));
+                player.bet(chips(5), &mut deck).unwrap();
+                assert!(!player.legal_actions().contains(&Action::Double));
+        }
+
+        #[test]
+        fn table_limits() {
+                let mut rules = Rules::new();
+                rules.min_bet = chips(5);
+                rules.max_spot_bet = Some(chips(50));
+                rules.max_bet = Some(chips(80));
+                let deck = Deck::from_notation("10S 7H 9C 8D 6S 5H").unwrap();
+                let mut table = Table::with_deck(deck, rules.clone());
+                table.add_player(Player::new(String::from("Player"), chips(500))).unwrap();
+                assert_eq!(Err(BlackjackError::InvalidWager(chips(-5))), table.bet(0, chips(-5)));
+                assert_eq!(Err(BlackjackError::BelowTableMinimum(chips(5))), table.bet(0, chips(4)));
+                assert_eq!(Err(BlackjackError::AboveTableMaximum(chips(50))), table.bet(0, chips(51)));
+                table.bet(0, chips(50)).unwrap();
+                assert_eq!(Err(BlackjackError::AboveTableMaximum(chips(80))), table.add_spot(0, chips(31)));
+                table.add_spot(0, chips(30)).unwrap();
+                assert_eq!(13, BlackjackError::AboveTableMaximum(chips(80)).code());
+
+                // Players enforce the same limits outside of a table
+                let mut deck = Deck::from_notation("10S 7H 9C 8D").unwrap();
+                let mut player = Player::with_rules(String::from("Player"), chips(500), rules);
+                assert_eq!(Err(BlackjackError::BelowTableMinimum(chips(5))), player.bet(chips(1), &mut deck));
+                player.bet(chips(50), &mut deck).unwrap();
+                assert_eq!(Err(BlackjackError::AboveTableMaximum(chips(80))), player.bet(chips(40), &mut deck));
+        }
+
+        #[test]
+        fn hand_value_breakdown() {
+                let mut deck = Deck::from_notation("AS 6H 8C 8D AH KD").unwrap();
+                let mut soft = Hand::new(chips(10), &mut deck).unwrap();
+                let pair = Hand::new(chips(10), &mut deck).unwrap();
+                let blackjack = Hand::new(chips(10), &mut deck).unwrap();
+                assert_eq!(HandValue {
+                        hard: 7, soft: 17, is_soft: true, is_blackjack: false, is_bust: false, is_pair: false
+                }, soft.get_value());
+                assert_eq!("7/17", soft.get_value().to_string());
+                assert_eq!(vec![7, 17], soft.get_value().totals());
+                assert_eq!(17, soft.value());
+                assert!(pair.get_value().is_pair);
+                assert_eq!("16", pair.get_value().to_string());
+                assert!(blackjack.get_value().is_blackjack);
+                assert_eq!(21, blackjack.get_value().total());
+
+                // Drawing a ten makes the soft 17 a hard 17, then a bust
+                let mut deck = Deck::from_notation("10C 9S").unwrap();
+                soft.hit(&mut deck).unwrap();
+                assert_eq!("17", soft.get_value().to_string());
+                assert!(!soft.is_soft());
+                soft.hit(&mut deck).unwrap();
+                assert!(soft.get_value().is_bust);
+                assert_eq!(vec![26], soft.get_value().totals());
+        }
+
+        #[test]
+        fn counting_systems() {
+                let systems: Vec<Box<dyn CountingSystem>> = vec![
+                        Box::new(HiLo), Box::new(HiOptI), Box::new(HiOptII),
+                        Box::new(OmegaII), Box::new(Zen), Box::new(WongHalves)
+                ];
+                for system in systems {
+                        // Balanced systems count a full shoe back to zero
+                        let mut counter = CardCounter::new(system, 2);
+                        for _ in 0..2 {
+                                Deck::create_valid_deck().iter().for_each(|card| counter.observe(card));
+                        }
+                        assert_eq!(0.0, counter.get_running_count(), "{}", counter.get_system_name());
+                }
+                let mut counter = CardCounter::new(Box::new(KnockOut), 2);
+                assert_eq!(-4.0, counter.get_running_count());
+                for _ in 0..2 {
+                        Deck::create_valid_deck().iter().for_each(|card| counter.observe(card));
+                }
+                assert_eq!(4.0, counter.get_running_count());
+
+                let mut counter = CardCounter::new(Box::new(WongHalves), 6);
+                counter.observe(&"5S".parse().unwrap());
+                counter.observe(&"7H".parse().unwrap());
+                assert_eq!(2.0, counter.get_running_count());
+                assert_eq!(6.0, counter.get_remaining_decks());
+                for _ in 0..26 * 4 {
+                        counter.observe(&"8D".parse().unwrap());
+                }
+                assert_eq!(4.0, counter.get_remaining_decks());
+                assert_eq!(0.5, counter.get_true_count());
+                assert_eq!(105, counter.get_seen(Rank::Eight) + counter.get_seen(Rank::Five));
+        }
+
+        #[test]
+        fn count_ignores_hole_card_until_revealed() {
+                let deck = Deck::from_notation("5S 6H KD 4C 3H").unwrap();
+                let mut table = Table::with_deck(deck, Rules::new());
+                table.attach_counter(Box::new(HiLo));
+                table.add_player(Player::new(String::from("Player"), chips(100))).unwrap();
+                table.bet(0, chips(10)).unwrap();
+                table.close_betting().unwrap();
+                table.deal().unwrap();
+                // Player's 5 and 6 plus the dealer's King; the 4 is face down
+                let counter = table.get_deck().get_counter().unwrap();
+                assert_eq!(1.0, counter.get_running_count());
+                assert_eq!(3, counter.get_cards_seen());
+                table.stand().unwrap();
+                table.play_dealer().unwrap();
+                let counter = table.get_deck().get_counter().unwrap();
+                assert_eq!(3.0, counter.get_running_count());
+                assert_eq!(5, counter.get_cards_seen());
+
+                let mut deck = Deck::from_notation("5S 6H").unwrap();
+                deck.attach_counter(Box::new(HiLo));
+                deck.next_card().unwrap();
+                deck.shuffle();
+                assert_eq!(0, deck.get_counter().unwrap().get_cards_seen());
         }
 }